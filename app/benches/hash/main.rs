@@ -4,7 +4,9 @@
  * // Use of this source code is governed by a BSD-style
  * // license that can be found in the LICENSE file.
  */
-use cith::{city_hash32, city_hash64, city_hash128, city_hash256_crc, city_murmur};
+use cith::{
+    Backend, city_hash32, city_hash64, city_hash128, city_hash256_crc, city_murmur, force_backend,
+};
 use criterion::{Criterion, criterion_group, criterion_main};
 
 pub fn criterion_benchmark(c: &mut Criterion) {
@@ -74,6 +76,16 @@ The current version, completed April 3, 2011, is MurmurHash3,[12][13] which yiel
             _ = city_hash256_crc(&v);
         })
     });
+
+    for backend in Backend::available() {
+        force_backend(backend).unwrap();
+        c.bench_function(&format!("city_hash256_{backend}"), |b| {
+            b.iter(|| {
+                _ = city_hash256_crc(&v);
+            })
+        });
+    }
+    force_backend(Backend::detect()).unwrap();
 }
 
 criterion_group!(benches, criterion_benchmark);
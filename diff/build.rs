@@ -0,0 +1,20 @@
+use std::env;
+
+fn main() {
+    let mut build = cc::Build::new();
+    build
+        .cpp(true)
+        .include("cityhash")
+        .file("cityhash/city.cc")
+        .file("cityhash/shim.cc")
+        .warnings(false);
+    // The CRC functions of the reference only exist with SSE4.2 on x86_64.
+    if env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("x86_64") {
+        build.flag_if_supported("-msse4.2");
+    }
+    if env::var("CARGO_CFG_TARGET_ENDIAN").as_deref() == Ok("big") {
+        build.define("WORDS_BIGENDIAN", None);
+    }
+    build.compile("cityhash_ref");
+    println!("cargo:rerun-if-changed=cityhash");
+}
@@ -0,0 +1,72 @@
+// C entry points over the reference CityHash, so Rust does not depend on the
+// C++ ABI of std::pair.
+
+#include "city.h"
+#ifdef __SSE4_2__
+#include "citycrc.h"
+#endif
+
+extern "C" {
+
+uint32 ref_city_hash32(const char *s, size_t len) {
+  return CityHash32(s, len);
+}
+
+uint64 ref_city_hash64(const char *s, size_t len) {
+  return CityHash64(s, len);
+}
+
+uint64 ref_city_hash64_with_seed(const char *s, size_t len, uint64 seed) {
+  return CityHash64WithSeed(s, len, seed);
+}
+
+uint64 ref_city_hash64_with_seeds(const char *s, size_t len, uint64 seed0,
+                                  uint64 seed1) {
+  return CityHash64WithSeeds(s, len, seed0, seed1);
+}
+
+uint64 ref_hash128_to_64(uint64 lo, uint64 hi) {
+  return Hash128to64(uint128(lo, hi));
+}
+
+void ref_city_hash128(const char *s, size_t len, uint64 *out) {
+  uint128 h = CityHash128(s, len);
+  out[0] = Uint128Low64(h);
+  out[1] = Uint128High64(h);
+}
+
+void ref_city_hash128_with_seed(const char *s, size_t len, uint64 seed_lo,
+                                uint64 seed_hi, uint64 *out) {
+  uint128 h = CityHash128WithSeed(s, len, uint128(seed_lo, seed_hi));
+  out[0] = Uint128Low64(h);
+  out[1] = Uint128High64(h);
+}
+
+#ifdef __SSE4_2__
+
+int ref_has_crc(void) { return 1; }
+
+void ref_city_hash_crc128(const char *s, size_t len, uint64 *out) {
+  uint128 h = CityHashCrc128(s, len);
+  out[0] = Uint128Low64(h);
+  out[1] = Uint128High64(h);
+}
+
+void ref_city_hash_crc128_with_seed(const char *s, size_t len, uint64 seed_lo,
+                                    uint64 seed_hi, uint64 *out) {
+  uint128 h = CityHashCrc128WithSeed(s, len, uint128(seed_lo, seed_hi));
+  out[0] = Uint128Low64(h);
+  out[1] = Uint128High64(h);
+}
+
+void ref_city_hash_crc256(const char *s, size_t len, uint64 *out) {
+  CityHashCrc256(s, len, out);
+}
+
+#else
+
+int ref_has_crc(void) { return 0; }
+
+#endif
+
+}  // extern "C"
@@ -0,0 +1,17 @@
+#![no_main]
+//! Feeds arbitrary input to every `cith` function and its C++ counterpart.
+//! The first 32 bytes become the seeds, the rest is hashed; libFuzzer's
+//! `-minimize_crash=1` reduces a divergence to a minimal reproducer.
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (seeds, bytes) = data.split_at(data.len().min(32));
+    let mut words = [0u8; 32];
+    words[..seeds.len()].copy_from_slice(seeds);
+    let seed0 = u64::from_le_bytes(words[0..8].try_into().unwrap());
+    let seed1 = u64::from_le_bytes(words[8..16].try_into().unwrap());
+    let seed128 = u128::from_le_bytes(words[16..32].try_into().unwrap());
+    if let Err(mismatch) = cith_diff::check(bytes, seed0, seed1, seed128) {
+        panic!("{mismatch}");
+    }
+});
@@ -0,0 +1,296 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Differential testing of `cith` against the reference C++ CityHash v1.1.
+//!
+//! [`reference`] calls the vendored `cityhash/city.cc`, compiled by the build
+//! script, and [`check`] compares every `cith` function with its C++
+//! counterpart on one input. The CRC functions of the reference need SSE4.2
+//! and are only compared on x86_64, see [`reference::has_crc`].
+use std::fmt;
+
+pub mod reference {
+    //! Safe wrappers over the reference implementation.
+    use std::ffi::{c_char, c_int};
+
+    unsafe extern "C" {
+        fn ref_city_hash32(s: *const c_char, len: usize) -> u32;
+        fn ref_city_hash64(s: *const c_char, len: usize) -> u64;
+        fn ref_city_hash64_with_seed(s: *const c_char, len: usize, seed: u64) -> u64;
+        fn ref_city_hash64_with_seeds(s: *const c_char, len: usize, seed0: u64, seed1: u64) -> u64;
+        fn ref_hash128_to_64(lo: u64, hi: u64) -> u64;
+        fn ref_city_hash128(s: *const c_char, len: usize, out: *mut u64);
+        fn ref_city_hash128_with_seed(
+            s: *const c_char,
+            len: usize,
+            seed_lo: u64,
+            seed_hi: u64,
+            out: *mut u64,
+        );
+        fn ref_has_crc() -> c_int;
+        fn ref_city_hash_crc128(s: *const c_char, len: usize, out: *mut u64);
+        fn ref_city_hash_crc128_with_seed(
+            s: *const c_char,
+            len: usize,
+            seed_lo: u64,
+            seed_hi: u64,
+            out: *mut u64,
+        );
+        fn ref_city_hash_crc256(s: *const c_char, len: usize, out: *mut u64);
+    }
+
+    fn join(words: [u64; 2]) -> u128 {
+        words[0] as u128 | ((words[1] as u128) << 64)
+    }
+
+    /// `CityHash32`.
+    pub fn city_hash32(bytes: &[u8]) -> u32 {
+        unsafe { ref_city_hash32(bytes.as_ptr().cast(), bytes.len()) }
+    }
+
+    /// `CityHash64`.
+    pub fn city_hash64(bytes: &[u8]) -> u64 {
+        unsafe { ref_city_hash64(bytes.as_ptr().cast(), bytes.len()) }
+    }
+
+    /// `CityHash64WithSeed`.
+    pub fn city_hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+        unsafe { ref_city_hash64_with_seed(bytes.as_ptr().cast(), bytes.len(), seed) }
+    }
+
+    /// `CityHash64WithSeeds`.
+    pub fn city_hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
+        unsafe { ref_city_hash64_with_seeds(bytes.as_ptr().cast(), bytes.len(), seed0, seed1) }
+    }
+
+    /// `Hash128to64`, with `Uint128Low64(x)` in the low half of `x`.
+    pub fn hash128_to_64(x: u128) -> u64 {
+        unsafe { ref_hash128_to_64(x as u64, (x >> 64) as u64) }
+    }
+
+    /// `CityHash128`, with `Uint128Low64` in the low half.
+    pub fn city_hash128(bytes: &[u8]) -> u128 {
+        let mut out = [0u64; 2];
+        unsafe { ref_city_hash128(bytes.as_ptr().cast(), bytes.len(), out.as_mut_ptr()) };
+        join(out)
+    }
+
+    /// `CityHash128WithSeed`.
+    pub fn city_hash128_with_seed(bytes: &[u8], seed: u128) -> u128 {
+        let mut out = [0u64; 2];
+        unsafe {
+            ref_city_hash128_with_seed(
+                bytes.as_ptr().cast(),
+                bytes.len(),
+                seed as u64,
+                (seed >> 64) as u64,
+                out.as_mut_ptr(),
+            )
+        };
+        join(out)
+    }
+
+    /// Whether the reference was built with the `citycrc.h` functions.
+    pub fn has_crc() -> bool {
+        unsafe { ref_has_crc() != 0 }
+    }
+
+    /// `CityHashCrc128`; panics unless [`has_crc`].
+    pub fn city_hash_crc128(bytes: &[u8]) -> u128 {
+        assert!(
+            has_crc(),
+            "the reference has no CRC functions on this target"
+        );
+        let mut out = [0u64; 2];
+        unsafe { ref_city_hash_crc128(bytes.as_ptr().cast(), bytes.len(), out.as_mut_ptr()) };
+        join(out)
+    }
+
+    /// `CityHashCrc128WithSeed`; panics unless [`has_crc`].
+    pub fn city_hash_crc128_with_seed(bytes: &[u8], seed: u128) -> u128 {
+        assert!(
+            has_crc(),
+            "the reference has no CRC functions on this target"
+        );
+        let mut out = [0u64; 2];
+        unsafe {
+            ref_city_hash_crc128_with_seed(
+                bytes.as_ptr().cast(),
+                bytes.len(),
+                seed as u64,
+                (seed >> 64) as u64,
+                out.as_mut_ptr(),
+            )
+        };
+        join(out)
+    }
+
+    /// `CityHashCrc256`; panics unless [`has_crc`].
+    pub fn city_hash_crc256(bytes: &[u8]) -> [u64; 4] {
+        assert!(
+            has_crc(),
+            "the reference has no CRC functions on this target"
+        );
+        let mut out = [0u64; 4];
+        unsafe { ref_city_hash_crc256(bytes.as_ptr().cast(), bytes.len(), out.as_mut_ptr()) };
+        out
+    }
+}
+
+/// A `cith` function that disagreed with the reference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The name of the upstream function.
+    pub function: &'static str,
+    /// The length of the input.
+    pub len: usize,
+    /// What the reference returned, formatted in hex.
+    pub expected: String,
+    /// What `cith` returned, formatted in hex.
+    pub actual: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} bytes: reference {}, cith {}",
+            self.function, self.len, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+fn compare<T: PartialEq + fmt::LowerHex>(
+    function: &'static str,
+    len: usize,
+    expected: T,
+    actual: T,
+) -> Result<(), Mismatch> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(Mismatch {
+            function,
+            len,
+            expected: format!("{expected:#x}"),
+            actual: format!("{actual:#x}"),
+        })
+    }
+}
+
+/// Compares every `cith` function on `bytes` with the reference, using `seed0`
+/// and `seed1` for the 64-bit seeded forms and `seed128` for the 128-bit ones.
+///
+/// Returns the first function that disagrees.
+pub fn check(bytes: &[u8], seed0: u64, seed1: u64, seed128: u128) -> Result<(), Mismatch> {
+    use reference as r;
+    let len = bytes.len();
+    compare(
+        "CityHash32",
+        len,
+        r::city_hash32(bytes),
+        cith::city_hash32(bytes),
+    )?;
+    compare(
+        "CityHash64",
+        len,
+        r::city_hash64(bytes),
+        cith::city_hash64(bytes),
+    )?;
+    compare(
+        "CityHash64WithSeed",
+        len,
+        r::city_hash64_with_seed(bytes, seed0),
+        cith::city_hash64_with_seed(bytes, seed0),
+    )?;
+    compare(
+        "CityHash64WithSeeds",
+        len,
+        r::city_hash64_with_seeds(bytes, seed0, seed1),
+        cith::city_hash64_with_seeds(bytes, seed0, seed1),
+    )?;
+    compare(
+        "Hash128to64",
+        len,
+        r::hash128_to_64(seed128),
+        cith::hash128_to_64(seed128),
+    )?;
+    compare(
+        "CityHash128",
+        len,
+        r::city_hash128(bytes),
+        cith::city_hash128(bytes),
+    )?;
+    compare(
+        "CityHash128WithSeed",
+        len,
+        r::city_hash128_with_seed(bytes, seed128),
+        cith::city_hash128_with_seed(bytes, seed128),
+    )?;
+    if len < 128 {
+        // CityHash128WithSeed is CityMurmur below 128 bytes.
+        compare(
+            "CityMurmur",
+            len,
+            r::city_hash128_with_seed(bytes, seed128),
+            cith::city_murmur_with_seed(bytes, seed128),
+        )?;
+    }
+    if r::has_crc() {
+        compare(
+            "CityHashCrc128",
+            len,
+            r::city_hash_crc128(bytes),
+            cith::city_hash128_crc(bytes),
+        )?;
+        compare(
+            "CityHashCrc128WithSeed",
+            len,
+            r::city_hash_crc128_with_seed(bytes, seed128),
+            cith::city_hash128_crc_with_seed(bytes, seed128),
+        )?;
+        let expected = r::city_hash_crc256(bytes);
+        let actual = cith::city_hash_crc256_words(bytes);
+        for (word, (&expected, &actual)) in expected.iter().zip(&actual).enumerate() {
+            compare(
+                [
+                    "CityHashCrc256[0]",
+                    "CityHashCrc256[1]",
+                    "CityHashCrc256[2]",
+                    "CityHashCrc256[3]",
+                ][word],
+                len,
+                expected,
+                actual,
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+//! Runs the differential comparison once per CRC backend available on the
+//! host. Forcing a backend is process wide, so this lives in its own binary.
+use cith::{Backend, force_backend};
+use cith_diff::check;
+
+#[test]
+fn test_every_backend() {
+    let data: Vec<u8> = (0..2048u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .collect();
+    for backend in Backend::available() {
+        force_backend(backend).unwrap();
+        for len in 0..=data.len() {
+            if let Err(mismatch) = check(&data[..len], 1234567, 0xc3a5c85c97cb3127, len as u128) {
+                panic!("{}: {mismatch}", backend.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Compares `cith` with the reference C++ on every length up to 4096 and on
+//! random inputs and seeds. proptest shrinks a divergence to the shortest
+//! input and records its seed under `proptest-regressions/`.
+use cith_diff::check;
+use proptest::prelude::*;
+
+const K0: u64 = 0xc3a5c85c97cb3127;
+
+/// Deterministic pseudo-random bytes, so a failing length is reproducible.
+fn data(len: usize) -> Vec<u8> {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_length() {
+    let data = data(4097);
+    let seeds = [
+        (0, 0, 0),
+        (1234567, K0, 1234567u128 | ((K0 as u128) << 64)),
+        (u64::MAX, u64::MAX, u128::MAX),
+    ];
+    for len in 0..=4096 {
+        for &(seed0, seed1, seed128) in &seeds {
+            if let Err(mismatch) = check(&data[..len], seed0, seed1, seed128) {
+                panic!("{mismatch}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_thresholds() {
+    // Each path switch of CityHash64/128 and the CRC variants, including the
+    // 240-byte CRC256 threshold and the 900-byte CRC128 switch.
+    let data = data(4096);
+    for boundary in [4, 8, 16, 32, 64, 128, 240, 256, 900, 1024] {
+        for len in boundary - 2..=boundary + 2 {
+            for offset in 0..8 {
+                let input = &data[offset..offset + len];
+                if let Err(mismatch) = check(input, len as u64, !(len as u64), len as u128) {
+                    panic!("offset {offset}: {mismatch}");
+                }
+            }
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2048))]
+
+    #[test]
+    fn prop_matches_reference(
+        bytes in prop::collection::vec(any::<u8>(), 0..4096),
+        seed0 in any::<u64>(),
+        seed1 in any::<u64>(),
+        seed128 in any::<u128>(),
+    ) {
+        let result = check(&bytes, seed0, seed1, seed128);
+        prop_assert!(result.is_ok(), "{}", result.unwrap_err());
+    }
+}
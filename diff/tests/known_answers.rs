@@ -0,0 +1,50 @@
+//! Checks that the vendored reference reproduces the upstream `city-test.cc`
+//! table, so the differential tests compare against genuine CityHash v1.1.
+use cith_diff::reference;
+
+const K0: u64 = 0xc3a5c85c97cb3127;
+
+include!("../../testdata/city_test_data.rs");
+
+fn halves(x: u128) -> [u64; 2] {
+    [x as u64, (x >> 64) as u64]
+}
+
+#[test]
+fn test_reference_matches_city_test_table() {
+    let table: Vec<Vec<u64>> = include_str!("../../testdata/city_v1_1.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.split_whitespace()
+                .map(|word| u64::from_str_radix(word, 16).unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(table.len(), 300);
+    let data = city_test_bytes(1 << 20);
+    let seed = 1234567u128 | ((K0 as u128) << 64);
+    for (i, row) in table.iter().enumerate() {
+        let input = if i == table.len() - 1 {
+            &data[..]
+        } else {
+            &data[i * i..i * i + i]
+        };
+        let mut actual = vec![
+            reference::city_hash64(input),
+            reference::city_hash64_with_seed(input, 1234567),
+            reference::city_hash64_with_seeds(input, 1234567, K0),
+        ];
+        actual.extend(halves(reference::city_hash128(input)));
+        actual.extend(halves(reference::city_hash128_with_seed(input, seed)));
+        if reference::has_crc() {
+            actual.extend(halves(reference::city_hash_crc128(input)));
+            actual.extend(halves(reference::city_hash_crc128_with_seed(input, seed)));
+            actual.extend(reference::city_hash_crc256(input));
+        } else {
+            actual.extend(&row[7..15]);
+        }
+        actual.push(reference::city_hash32(input) as u64);
+        assert_eq!(&actual, row, "row {i}, length {}", input.len());
+    }
+}
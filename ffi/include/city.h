@@ -0,0 +1,93 @@
+#ifndef CITY_HASH_H_
+#define CITY_HASH_H_
+
+/* Generated by cbindgen from ffi/src/lib.rs; do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+typedef uint8_t uint8;
+typedef uint32_t uint32;
+typedef uint64_t uint64;
+
+/**
+ * A 128-bit hash, laid out like the C++ `std::pair<uint64, uint64>`.
+ */
+typedef struct {
+  /**
+   * The low 64 bits, `Uint128Low64`.
+   */
+  uint64_t first;
+  /**
+   * The high 64 bits, `Uint128High64`.
+   */
+  uint64_t second;
+} uint128;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Hash function for a byte array.
+ */
+uint64_t CityHash64(const char *s, size_t len);
+
+/**
+ * Hash function for a byte array.  For convenience, a 64-bit seed is also
+ * hashed into the result.
+ */
+uint64_t CityHash64WithSeed(const char *s, size_t len, uint64_t seed);
+
+/**
+ * Hash function for a byte array.  For convenience, two seeds are also
+ * hashed into the result.
+ */
+uint64_t CityHash64WithSeeds(const char *s, size_t len, uint64_t seed0, uint64_t seed1);
+
+/**
+ * Hash function for a byte array.
+ */
+uint128 CityHash128(const char *s, size_t len);
+
+/**
+ * Hash function for a byte array.  For convenience, a 128-bit seed is also
+ * hashed into the result.
+ */
+uint128 CityHash128WithSeed(const char *s, size_t len, uint128 seed);
+
+/**
+ * Hash function for a byte array.  Most useful in 32-bit binaries.
+ */
+uint32_t CityHash32(const char *s, size_t len);
+
+/**
+ * Hash 128 input bits down to 64 bits of output.
+ * This is intended to be a reasonably good hash function.
+ */
+uint64_t Hash128to64(uint128 x);
+
+/**
+ * Hash function for a byte array.
+ */
+uint128 CityHashCrc128(const char *s, size_t len);
+
+/**
+ * Hash function for a byte array.  For convenience, a 128-bit seed is also
+ * hashed into the result.
+ */
+uint128 CityHashCrc128WithSeed(const char *s, size_t len, uint128 seed);
+
+/**
+ * Hash function for a byte array.  Sets result[0] ... result[3].
+ */
+void CityHashCrc256(const char *s, size_t len, uint64_t *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+static inline uint64 Uint128Low64(uint128 x) { return x.first; }
+static inline uint64 Uint128High64(uint128 x) { return x.second; }
+
+#endif  /* CITY_HASH_H_ */
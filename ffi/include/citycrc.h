@@ -0,0 +1,7 @@
+/* Declarations of citycrc.h; the functions live in city.h alongside the rest. */
+#ifndef CITY_HASH_CRC_H_
+#define CITY_HASH_CRC_H_
+
+#include "city.h"
+
+#endif  /* CITY_HASH_CRC_H_ */
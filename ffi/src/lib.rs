@@ -0,0 +1,187 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! C ABI of `cith`, a drop-in replacement for Google's `libcityhash`.
+//!
+//! The exported functions have the names and signatures of `city.h` and
+//! `citycrc.h` from CityHash v1.1; `include/city.h` is generated from this
+//! file by the build script. `uint128` is a struct with the `first` (low) and
+//! `second` (high) members of the C++ `std::pair`.
+//!
+//! The replacement is drop-in for C only. The struct has the layout of
+//! `std::pair<uint64, uint64>` but is not that type, so C++ code that builds
+//! or passes a `uint128` as a `std::pair` does not compile against this
+//! header; code that only reads `.first` and `.second` or calls
+//! `Uint128Low64` and `Uint128High64` does.
+//!
+//! Every function takes a pointer and a length; the pointer may be null when
+//! the length is zero.
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::c_char;
+
+/// A 128-bit hash, laid out like the C++ `std::pair<uint64, uint64>`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Uint128 {
+    /// The low 64 bits, `Uint128Low64`.
+    pub first: u64,
+    /// The high 64 bits, `Uint128High64`.
+    pub second: u64,
+}
+
+impl From<u128> for Uint128 {
+    fn from(value: u128) -> Self {
+        Uint128 {
+            first: value as u64,
+            second: (value >> 64) as u64,
+        }
+    }
+}
+
+impl From<Uint128> for u128 {
+    fn from(value: Uint128) -> Self {
+        value.first as u128 | ((value.second as u128) << 64)
+    }
+}
+
+/// Borrows the `len` bytes at `s`, allowing a null `s` when `len` is zero.
+#[inline]
+unsafe fn bytes<'a>(s: *const c_char, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(s.cast(), len) }
+    }
+}
+
+/// Hash function for a byte array.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHash64(s: *const c_char, len: usize) -> u64 {
+    cith::city_hash64(unsafe { bytes(s, len) })
+}
+
+/// Hash function for a byte array.  For convenience, a 64-bit seed is also
+/// hashed into the result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHash64WithSeed(s: *const c_char, len: usize, seed: u64) -> u64 {
+    cith::city_hash64_with_seed(unsafe { bytes(s, len) }, seed)
+}
+
+/// Hash function for a byte array.  For convenience, two seeds are also
+/// hashed into the result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHash64WithSeeds(
+    s: *const c_char,
+    len: usize,
+    seed0: u64,
+    seed1: u64,
+) -> u64 {
+    cith::city_hash64_with_seeds(unsafe { bytes(s, len) }, seed0, seed1)
+}
+
+/// Hash function for a byte array.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHash128(s: *const c_char, len: usize) -> Uint128 {
+    cith::city_hash128(unsafe { bytes(s, len) }).into()
+}
+
+/// Hash function for a byte array.  For convenience, a 128-bit seed is also
+/// hashed into the result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHash128WithSeed(
+    s: *const c_char,
+    len: usize,
+    seed: Uint128,
+) -> Uint128 {
+    cith::city_hash128_with_seed(unsafe { bytes(s, len) }, seed.into()).into()
+}
+
+/// Hash function for a byte array.  Most useful in 32-bit binaries.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHash32(s: *const c_char, len: usize) -> u32 {
+    cith::city_hash32(unsafe { bytes(s, len) })
+}
+
+/// Hash 128 input bits down to 64 bits of output.
+/// This is intended to be a reasonably good hash function.
+#[unsafe(no_mangle)]
+pub extern "C" fn Hash128to64(x: Uint128) -> u64 {
+    cith::hash128_to_64(x.into())
+}
+
+/// Hash function for a byte array.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHashCrc128(s: *const c_char, len: usize) -> Uint128 {
+    cith::city_hash128_crc(unsafe { bytes(s, len) }).into()
+}
+
+/// Hash function for a byte array.  For convenience, a 128-bit seed is also
+/// hashed into the result.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHashCrc128WithSeed(
+    s: *const c_char,
+    len: usize,
+    seed: Uint128,
+) -> Uint128 {
+    cith::city_hash128_crc_with_seed(unsafe { bytes(s, len) }, seed.into()).into()
+}
+
+/// Hash function for a byte array.  Sets result[0] ... result[3].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn CityHashCrc256(s: *const c_char, len: usize, result: *mut u64) {
+    let words = cith::city_hash_crc256_words(unsafe { bytes(s, len) });
+    unsafe { std::ptr::copy_nonoverlapping(words.as_ptr(), result, 4) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_crate() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let (s, len) = (data.as_ptr().cast(), data.len());
+        unsafe {
+            assert_eq!(CityHash32(s, len), cith::city_hash32(data));
+            assert_eq!(CityHash64(s, len), cith::city_hash64(data));
+            assert_eq!(u128::from(CityHash128(s, len)), cith::city_hash128(data));
+            let mut result = [0u64; 4];
+            CityHashCrc256(s, len, result.as_mut_ptr());
+            assert_eq!(result, cith::city_hash_crc256_words(data));
+        }
+    }
+
+    #[test]
+    fn test_null_empty() {
+        unsafe {
+            assert_eq!(CityHash64(std::ptr::null(), 0), 0x9ae16a3b2f90404f);
+            assert_eq!(CityHash32(std::ptr::null(), 0), cith::city_hash32(&[]));
+        }
+    }
+}
@@ -0,0 +1,130 @@
+/*
+ * Checks the C ABI of cith against rows of the CityHash v1.1 city-test.cc
+ * table, using the same pseudo-random input.
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "city.h"
+#include "citycrc.h"
+
+static const uint64 k0 = 0xc3a5c85c97cb3127ull;
+static const uint64 kSeed0 = 1234567;
+static const uint64 kSeed1 = 0xc3a5c85c97cb3127ull;
+static const int kDataSize = 1 << 20;
+
+static char data[1 << 20];
+
+struct row {
+  int index;
+  uint32 hash32;
+  uint64 hash64[3];
+  uint64 hash128[4];
+  uint64 crc128[4];
+  uint64 crc256[4];
+};
+
+static const struct row rows[] = {
+    {0, 0xdc56d17au,
+     {0x9ae16a3b2f90404full, 0x75106db890237a4aull, 0x3feac5f636039766ull},
+     {0x3df09dfc64c09a2bull, 0x3cb540c392e51e29ull, 0x06b56343feac0663ull, 0x5b7bc50fd8e8ad92ull},
+     {0x3df09dfc64c09a2bull, 0x3cb540c392e51e29ull, 0x06b56343feac0663ull, 0x5b7bc50fd8e8ad92ull},
+     {0x95162f24e6a5f930ull, 0x6808bdf4f1eb06e0ull, 0xb3b1f3a67b624d82ull, 0xc9a62f12bd4cd80bull}},
+    {3, 0xebc34f3cu,
+     {0xef923a7a1af78eabull, 0x79163b1e1e9a9b18ull, 0xdf3b2aca6e1e4a30ull},
+     {0x2193fb7620cbf23bull, 0x8b6a8ff06cda8302ull, 0x1a44469afd3e091full, 0x08b0449376612506ull},
+     {0x2193fb7620cbf23bull, 0x8b6a8ff06cda8302ull, 0x1a44469afd3e091full, 0x08b0449376612506ull},
+     {0xe9d9d41c32ad91d1ull, 0xb44ab09f58e3c608ull, 0x019e9175f9fcf784ull, 0x839b3c9581b4a480ull}},
+    {40, 0x72c39beau,
+     {0x4ec0b54cf1566affull, 0x30d2c7269b206bf4ull, 0x77c22e82295e1061ull},
+     {0x3df9b04434771542ull, 0xfeddce785ccb661full, 0xa644aff716928297ull, 0xdd46aee73824b4edull},
+     {0x3df9b04434771542ull, 0xfeddce785ccb661full, 0xa644aff716928297ull, 0xdd46aee73824b4edull},
+     {0xbf8d71879da29b02ull, 0xfc82dccbfc8022a0ull, 0x31bfcd0d9f48d1d3ull, 0xc64ee24d0e7b5f8bull}},
+    {150, 0xbe139231u,
+     {0x1de2119923e8ef3cull, 0x6ab27c096cf2fe14ull, 0x8c3658edca958891ull},
+     {0x04cc8ed3ada5f0f2ull, 0x4a496b77c1f1c04eull, 0x9085b0a862084201ull, 0xa1894bde9e3dee21ull},
+     {0x04cc8ed3ada5f0f2ull, 0x4a496b77c1f1c04eull, 0x9085b0a862084201ull, 0xa1894bde9e3dee21ull},
+     {0x367fb472dc5b277dull, 0x7d39ccca16fc6745ull, 0x763f988d70db9106ull, 0xa8b66f7fecb70f02ull}},
+    {299, 0x5398210cu,
+     {0x5fb5e48ac7b7fa4full, 0xa96170f08f5acbc7ull, 0xbbf5c63d4f52a1e5ull},
+     {0x6cc09e60700563e9ull, 0xd18f23221e964791ull, 0xffc23eeef7af26ebull, 0x693a954a3622a315ull},
+     {0x815308a32a9b0dafull, 0xefb2ab27bf6fd0bdull, 0x9f1ffc0986111118ull, 0xf9a3aa1778ea3985ull},
+     {0x698fe54b2b93933bull, 0xdacc2b28404d0f10ull, 0x815308a32a9b0dafull, 0xefb2ab27bf6fd0bdull}},
+};
+
+static int failures = 0;
+
+static void check64(int index, const char *name, uint64 expected, uint64 actual) {
+  if (expected != actual) {
+    fprintf(stderr, "row %d: %s: expected %016llx, got %016llx\n", index, name,
+            (unsigned long long)expected, (unsigned long long)actual);
+    failures++;
+  }
+}
+
+static void setup(void) {
+  uint64 a = 9;
+  uint64 b = 777;
+  int i;
+  for (i = 0; i < kDataSize; i++) {
+    a += b;
+    b += a;
+    a = (a ^ (a >> 41)) * k0;
+    b = (b ^ (b >> 41)) * k0 + i;
+    data[i] = (char)(b >> 37);
+  }
+}
+
+static void test_row(const struct row *r) {
+  const char *s = r->index == 299 ? data : data + r->index * r->index;
+  size_t len = r->index == 299 ? (size_t)kDataSize : (size_t)r->index;
+  uint128 seed = {kSeed0, kSeed1};
+  uint128 u;
+  uint64 crc[4];
+  int i;
+
+  check64(r->index, "CityHash32", r->hash32, CityHash32(s, len));
+  check64(r->index, "CityHash64", r->hash64[0], CityHash64(s, len));
+  check64(r->index, "CityHash64WithSeed", r->hash64[1], CityHash64WithSeed(s, len, kSeed0));
+  check64(r->index, "CityHash64WithSeeds", r->hash64[2],
+          CityHash64WithSeeds(s, len, kSeed0, kSeed1));
+
+  u = CityHash128(s, len);
+  check64(r->index, "CityHash128 low", r->hash128[0], Uint128Low64(u));
+  check64(r->index, "CityHash128 high", r->hash128[1], Uint128High64(u));
+  u = CityHash128WithSeed(s, len, seed);
+  check64(r->index, "CityHash128WithSeed low", r->hash128[2], Uint128Low64(u));
+  check64(r->index, "CityHash128WithSeed high", r->hash128[3], Uint128High64(u));
+
+  u = CityHashCrc128(s, len);
+  check64(r->index, "CityHashCrc128 low", r->crc128[0], Uint128Low64(u));
+  check64(r->index, "CityHashCrc128 high", r->crc128[1], Uint128High64(u));
+  u = CityHashCrc128WithSeed(s, len, seed);
+  check64(r->index, "CityHashCrc128WithSeed low", r->crc128[2], Uint128Low64(u));
+  check64(r->index, "CityHashCrc128WithSeed high", r->crc128[3], Uint128High64(u));
+
+  CityHashCrc256(s, len, crc);
+  for (i = 0; i < 4; i++) {
+    check64(r->index, "CityHashCrc256", r->crc256[i], crc[i]);
+  }
+}
+
+int main(void) {
+  size_t i;
+
+  setup();
+  for (i = 0; i < sizeof(rows) / sizeof(rows[0]); i++) {
+    test_row(&rows[i]);
+  }
+  check64(-1, "CityHash64 of NULL", 0x9ae16a3b2f90404full, CityHash64(NULL, 0));
+  check64(-1, "Hash128to64", CityHash64WithSeeds("abc", 3, 5, 7),
+          Hash128to64((uint128){CityHash64("abc", 3) - 5, 7}));
+
+  if (failures != 0) {
+    fprintf(stderr, "%d checks failed\n", failures);
+    return EXIT_FAILURE;
+  }
+  printf("all checks passed\n");
+  return EXIT_SUCCESS;
+}
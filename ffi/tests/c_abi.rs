@@ -0,0 +1,48 @@
+//! Builds `tests/c/city_test.c` against `include/city.h` and the static
+//! library, and runs it.
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The directory holding `libcityhash.a`, two levels above this test binary.
+fn artifact_dir() -> PathBuf {
+    let exe = std::env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+#[cfg(all(unix, not(miri)))]
+fn test_c_program() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = env!("CITH_FFI_TARGET");
+    let out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("city_test");
+    let compiler = cc::Build::new()
+        .target(target)
+        .host(target)
+        .opt_level(0)
+        .cargo_metadata(false)
+        .get_compiler();
+
+    let mut command = compiler.to_command();
+    command
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(manifest.join("include"))
+        .arg(manifest.join("tests/c/city_test.c"))
+        .arg(artifact_dir().join("libcityhash.a"))
+        .arg("-o")
+        .arg(&out);
+    if !cfg!(target_os = "macos") {
+        command.args(["-lpthread", "-ldl", "-lm"]);
+    }
+    let status = command.status().expect("failed to run the C compiler");
+    assert!(status.success(), "compiling city_test.c failed");
+
+    let output = Command::new(&out).output().unwrap();
+    assert!(
+        output.status.success(),
+        "city_test failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
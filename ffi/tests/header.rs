@@ -0,0 +1,13 @@
+//! The committed `include/city.h` must be what cbindgen generates from the
+//! current sources; the build script writes its output to `OUT_DIR`.
+
+#[test]
+fn test_header_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/city.h"));
+    let committed = include_str!("../include/city.h");
+    assert!(
+        generated == committed,
+        "include/city.h is stale; copy {}/city.h over it",
+        env!("OUT_DIR")
+    );
+}
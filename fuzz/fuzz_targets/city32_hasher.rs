@@ -0,0 +1,12 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+use std::hash::Hasher;
+
+fuzz_target!(|input: Input| {
+    let mut hasher = cith::City32Hasher::default();
+    let (head, tail) = input.split();
+    hasher.write(head);
+    hasher.write(tail);
+    assert_eq!(hasher.finish(), cith::city_hash32(input.bytes) as u64);
+});
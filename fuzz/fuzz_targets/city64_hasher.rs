@@ -0,0 +1,16 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+use std::hash::Hasher;
+
+fuzz_target!(|input: Input| {
+    let mut hasher = cith::City64Hasher::new_with_seed(input.seed);
+    let (head, tail) = input.split();
+    hasher.write(head);
+    hasher.write(tail);
+    let expected = match input.seed {
+        0 => cith::city_hash64(input.bytes),
+        seed => cith::city_hash64_with_seed(input.bytes, seed),
+    };
+    assert_eq!(hasher.finish(), expected);
+});
@@ -0,0 +1,7 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    cith::city_hash128_crc_with_seed(input.bytes, input.seed128);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    let seeds = [input.seed128, input.seed as u128];
+    let mut out = [0u128; 2];
+    cith::city_hash128_crc_with_seeds_many(input.bytes, &seeds, &mut out);
+    assert_eq!(
+        out[0],
+        cith::city_hash128_crc_with_seed(input.bytes, input.seed128)
+    );
+});
@@ -0,0 +1,10 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    let seeds = [input.seed, input.seed1];
+    let mut out = [0u64; 2];
+    cith::city_hash64_with_seeds_many(input.bytes, &seeds, &mut out);
+    assert_eq!(out[0], cith::city_hash64_with_seed(input.bytes, input.seed));
+});
@@ -0,0 +1,15 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    macro_rules! fixed {
+        ($($n:literal)*) => {$(
+            if let Some(prefix) = input.bytes.first_chunk::<$n>() {
+                assert_eq!(cith::city_hash64_fixed(prefix), cith::city_hash64(prefix));
+                assert_eq!(cith::city_hash128_fixed(prefix), cith::city_hash128(prefix));
+            }
+        )*};
+    }
+    fixed!(0 1 2 3 4 5 6 7 8 9 12 15 16 17 24 31 32 33 48 63 64 65 127 128 129 255 256);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    let mut tree = cith::CityTree128::new();
+    let (head, tail) = input.split();
+    tree.update(head);
+    tree.update(tail);
+    assert_eq!(tree.finalize(), cith::CityTree128::hash(input.bytes));
+});
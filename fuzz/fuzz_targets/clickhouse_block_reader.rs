@@ -0,0 +1,8 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    let mut reader = cith::clickhouse::block::BlockReader::new(input.bytes);
+    while let Ok(Some(_)) = reader.read_block() {}
+});
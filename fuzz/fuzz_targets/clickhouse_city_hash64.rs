@@ -0,0 +1,18 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    use cith::clickhouse::Value;
+    let (head, tail) = input.split();
+    let values = [
+        Value::String(head),
+        Value::UInt64(input.seed),
+        Value::Int32(input.seed1 as i32),
+    ];
+    cith::clickhouse::city_hash64(&[
+        Value::FixedString(tail),
+        Value::Array(&values),
+        Value::Tuple(&values),
+    ]);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    use cith::clickhouse::block::{BlockReader, Method, encode_block};
+    let block = encode_block(Method::None, input.bytes);
+    let mut reader = BlockReader::new(block.as_slice());
+    assert_eq!(reader.read_block().unwrap().as_deref(), Some(input.bytes));
+});
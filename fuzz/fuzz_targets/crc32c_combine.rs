@@ -0,0 +1,7 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    cith::crc32c::crc32c_combine(input.seed as u32, input.seed1 as u32, input.seed128 as u64);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    let mut crc = cith::crc32c::Crc32c::new();
+    let (head, tail) = input.split();
+    crc.update(head);
+    crc.update(tail);
+    assert_eq!(crc.finalize(), cith::crc32c::crc32c(input.bytes));
+});
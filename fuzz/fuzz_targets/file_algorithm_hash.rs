@@ -0,0 +1,19 @@
+#![no_main]
+use cith_fuzz::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: Input| {
+    use cith::file::Algorithm;
+    for algorithm in [
+        Algorithm::City32,
+        Algorithm::City64,
+        Algorithm::City128,
+        Algorithm::City128Crc,
+        Algorithm::City256Crc,
+        Algorithm::Tree128,
+        Algorithm::Tree256,
+        Algorithm::Crc32c,
+    ] {
+        algorithm.hash(input.bytes);
+    }
+});
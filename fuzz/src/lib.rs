@@ -0,0 +1,20 @@
+//! The input shared by the fuzz targets: seeds of every width followed by the
+//! bytes to hash.
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+
+#[derive(Arbitrary, Debug)]
+pub struct Input<'a> {
+    pub seed: u64,
+    pub seed1: u64,
+    pub seed128: u128,
+    pub bytes: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    /// Splits the bytes at a position chosen by the seed, for the targets
+    /// that write in two pieces.
+    pub fn split(&self) -> (&'a [u8], &'a [u8]) {
+        self.bytes
+            .split_at((self.seed1 % (self.bytes.len() as u64 + 1)) as usize)
+    }
+}
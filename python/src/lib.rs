@@ -0,0 +1,291 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Python bindings of `cith`, a drop-in replacement for the `cityhash` package.
+//!
+//! `CityHash32`, `CityHash64`, `CityHash64WithSeed`, `CityHash64WithSeeds`,
+//! `CityHash128` and `CityHash128WithSeed` have the names, default seeds and
+//! return values of the functions in `cityhash`: plain Python ints, with a
+//! 128-bit hash packed as `low << 64 | high`, the `first` word of the C++
+//! `uint128` on top. A 128-bit seed splits the same way, into
+//! `first = seed >> 64` and `second = seed & (2**64 - 1)`. Input is a `str`,
+//! hashed as its UTF-8 encoding, or any object exporting a C-contiguous
+//! buffer, which is hashed in place without a copy.
+//!
+//! `City32Hasher`, `City64Hasher` and `City128Hasher` accumulate `update`
+//! calls and hash the concatenation, so splitting the input never changes
+//! the result.
+#![allow(non_snake_case)]
+
+use pyo3::ffi;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use std::mem::MaybeUninit;
+
+/// A buffer exported by a Python object, released on drop.
+struct BufferView(ffi::Py_buffer);
+
+impl BufferView {
+    fn get(data: &Bound<'_, PyAny>) -> PyResult<BufferView> {
+        let mut view = MaybeUninit::<ffi::Py_buffer>::uninit();
+        // PyBUF_SIMPLE asks for a C-contiguous block of bytes of any format
+        // and raises BufferError for objects that cannot provide one.
+        let status =
+            unsafe { ffi::PyObject_GetBuffer(data.as_ptr(), view.as_mut_ptr(), ffi::PyBUF_SIMPLE) };
+        if status == -1 {
+            return Err(PyErr::fetch(data.py()));
+        }
+        Ok(BufferView(unsafe { view.assume_init() }))
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        let len = self.0.len as usize;
+        if len == 0 {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.0.buf.cast(), len) }
+        }
+    }
+}
+
+impl Drop for BufferView {
+    fn drop(&mut self) {
+        unsafe { ffi::PyBuffer_Release(&mut self.0) };
+    }
+}
+
+/// Calls `f` with the bytes of `data`: the UTF-8 encoding of a `str`, or the
+/// contents of a buffer.
+fn with_bytes<R>(data: &Bound<'_, PyAny>, f: impl FnOnce(&[u8]) -> R) -> PyResult<R> {
+    if let Ok(s) = data.cast::<PyString>() {
+        return Ok(f(s.to_str()?.as_bytes()));
+    }
+    let view = BufferView::get(data)?;
+    Ok(f(view.as_slice()))
+}
+
+/// Converts between the crate's `low | high << 64` and the `cityhash`
+/// package's `low << 64 | high`, in either direction.
+fn swap_halves(x: u128) -> u128 {
+    x.rotate_left(64)
+}
+
+/// Hash function for a byte array. Most useful in 32-bit binaries.
+#[pyfunction]
+fn CityHash32(data: &Bound<'_, PyAny>) -> PyResult<u32> {
+    with_bytes(data, cith::city_hash32)
+}
+
+/// Hash function for a byte array.
+#[pyfunction]
+fn CityHash64(data: &Bound<'_, PyAny>) -> PyResult<u64> {
+    with_bytes(data, cith::city_hash64)
+}
+
+/// Hash function for a byte array. For convenience, a 64-bit seed is also
+/// hashed into the result.
+#[pyfunction]
+#[pyo3(signature = (data, seed = 0))]
+fn CityHash64WithSeed(data: &Bound<'_, PyAny>, seed: u64) -> PyResult<u64> {
+    with_bytes(data, |bytes| cith::city_hash64_with_seed(bytes, seed))
+}
+
+/// Hash function for a byte array. For convenience, two seeds are also
+/// hashed into the result.
+#[pyfunction]
+#[pyo3(signature = (data, seed0 = 0, seed1 = 0))]
+fn CityHash64WithSeeds(data: &Bound<'_, PyAny>, seed0: u64, seed1: u64) -> PyResult<u64> {
+    with_bytes(data, |bytes| {
+        cith::city_hash64_with_seeds(bytes, seed0, seed1)
+    })
+}
+
+/// Hash function for a byte array.
+#[pyfunction]
+fn CityHash128(data: &Bound<'_, PyAny>) -> PyResult<u128> {
+    with_bytes(data, |bytes| swap_halves(cith::city_hash128(bytes)))
+}
+
+/// Hash function for a byte array. For convenience, a 128-bit seed is also
+/// hashed into the result.
+#[pyfunction]
+#[pyo3(signature = (data, seed = 0))]
+fn CityHash128WithSeed(data: &Bound<'_, PyAny>, seed: u128) -> PyResult<u128> {
+    with_bytes(data, |bytes| {
+        swap_halves(cith::city_hash128_with_seed(bytes, swap_halves(seed)))
+    })
+}
+
+/// Incremental `CityHash32`.
+#[pyclass(module = "cityhash")]
+#[derive(Clone, Default)]
+struct City32Hasher {
+    bytes: Vec<u8>,
+}
+
+#[pymethods]
+impl City32Hasher {
+    #[new]
+    #[pyo3(signature = (data = None))]
+    fn new(data: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut hasher = Self::default();
+        if let Some(data) = data {
+            hasher.update(data)?;
+        }
+        Ok(hasher)
+    }
+
+    /// Appends `data` to the hashed input.
+    fn update(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        with_bytes(data, |bytes| self.bytes.extend_from_slice(bytes))
+    }
+
+    /// Returns the hash of everything passed to `update` so far.
+    fn intdigest(&self) -> u32 {
+        cith::city_hash32(&self.bytes)
+    }
+
+    /// Returns `intdigest()` as 8 hexadecimal digits.
+    fn hexdigest(&self) -> String {
+        format!("{:08x}", self.intdigest())
+    }
+
+    /// Returns an independent copy of the hasher.
+    fn copy(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Incremental `CityHash64`, or `CityHash64WithSeed` when a seed is given.
+#[pyclass(module = "cityhash")]
+#[derive(Clone, Default)]
+struct City64Hasher {
+    bytes: Vec<u8>,
+    seed: Option<u64>,
+}
+
+#[pymethods]
+impl City64Hasher {
+    #[new]
+    #[pyo3(signature = (data = None, seed = None))]
+    fn new(data: Option<&Bound<'_, PyAny>>, seed: Option<u64>) -> PyResult<Self> {
+        let mut hasher = Self {
+            bytes: Vec::new(),
+            seed,
+        };
+        if let Some(data) = data {
+            hasher.update(data)?;
+        }
+        Ok(hasher)
+    }
+
+    /// Appends `data` to the hashed input.
+    fn update(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        with_bytes(data, |bytes| self.bytes.extend_from_slice(bytes))
+    }
+
+    /// Returns the hash of everything passed to `update` so far.
+    fn intdigest(&self) -> u64 {
+        match self.seed {
+            Some(seed) => cith::city_hash64_with_seed(&self.bytes, seed),
+            None => cith::city_hash64(&self.bytes),
+        }
+    }
+
+    /// Returns `intdigest()` as 16 hexadecimal digits.
+    fn hexdigest(&self) -> String {
+        format!("{:016x}", self.intdigest())
+    }
+
+    /// Returns an independent copy of the hasher.
+    fn copy(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Incremental `CityHash128`, or `CityHash128WithSeed` when a seed is given.
+#[pyclass(module = "cityhash")]
+#[derive(Clone, Default)]
+struct City128Hasher {
+    bytes: Vec<u8>,
+    seed: Option<u128>,
+}
+
+#[pymethods]
+impl City128Hasher {
+    #[new]
+    #[pyo3(signature = (data = None, seed = None))]
+    fn new(data: Option<&Bound<'_, PyAny>>, seed: Option<u128>) -> PyResult<Self> {
+        let mut hasher = Self {
+            bytes: Vec::new(),
+            seed,
+        };
+        if let Some(data) = data {
+            hasher.update(data)?;
+        }
+        Ok(hasher)
+    }
+
+    /// Appends `data` to the hashed input.
+    fn update(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        with_bytes(data, |bytes| self.bytes.extend_from_slice(bytes))
+    }
+
+    /// Returns the hash of everything passed to `update` so far.
+    fn intdigest(&self) -> u128 {
+        swap_halves(match self.seed {
+            Some(seed) => cith::city_hash128_with_seed(&self.bytes, swap_halves(seed)),
+            None => cith::city_hash128(&self.bytes),
+        })
+    }
+
+    /// Returns `intdigest()` as 32 hexadecimal digits.
+    fn hexdigest(&self) -> String {
+        format!("{:032x}", self.intdigest())
+    }
+
+    /// Returns an independent copy of the hasher.
+    fn copy(&self) -> Self {
+        self.clone()
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "cityhash")]
+fn cith_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(CityHash32, m)?)?;
+    m.add_function(wrap_pyfunction!(CityHash64, m)?)?;
+    m.add_function(wrap_pyfunction!(CityHash64WithSeed, m)?)?;
+    m.add_function(wrap_pyfunction!(CityHash64WithSeeds, m)?)?;
+    m.add_function(wrap_pyfunction!(CityHash128, m)?)?;
+    m.add_function(wrap_pyfunction!(CityHash128WithSeed, m)?)?;
+    m.add_class::<City32Hasher>()?;
+    m.add_class::<City64Hasher>()?;
+    m.add_class::<City128Hasher>()?;
+    Ok(())
+}
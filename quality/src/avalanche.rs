@@ -0,0 +1,162 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Batteries flipping one bit of the key or seed at a time.
+use crate::{Candidate, Config, Outcome, Rng, bias_limit, collisions_of};
+
+/// Adds one to `row[bit]` for every set bit of the difference of two outputs.
+fn count_flips(row: &mut [u32], a: &[u64; 4], b: &[u64; 4]) {
+    for (word, (a, b)) in a.iter().zip(b).enumerate() {
+        let mut diff = a ^ b;
+        while diff != 0 {
+            row[word * 64 + diff.trailing_zeros() as usize] += 1;
+            diff &= diff - 1;
+        }
+    }
+}
+
+/// Worst `|2p - 1|` over the flip counts, where `p` should be one half.
+fn worst_bias(counts: &[u32], reps: usize) -> f64 {
+    counts
+        .iter()
+        .map(|&count| (2.0 * count as f64 / reps as f64 - 1.0).abs())
+        .fold(0.0, f64::max)
+}
+
+/// Strict avalanche criterion: flipping any key bit flips every output bit
+/// with probability one half.
+pub(crate) fn avalanche(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    let mut rng = Rng::new(1);
+    config
+        .avalanche_lengths
+        .iter()
+        .map(|&len| {
+            let in_bits = len * 8;
+            let mut counts = vec![0u32; in_bits * candidate.bits];
+            let mut key = vec![0u8; len];
+            for _ in 0..config.avalanche_reps {
+                rng.fill(&mut key);
+                let base = (candidate.hash)(&key, 0);
+                for (bit, row) in counts.chunks_exact_mut(candidate.bits).enumerate() {
+                    key[bit / 8] ^= 1 << (bit % 8);
+                    count_flips(row, &base, &(candidate.hash)(&key, 0));
+                    key[bit / 8] ^= 1 << (bit % 8);
+                }
+            }
+            Outcome {
+                battery: "avalanche",
+                case: format!("{len}-byte keys"),
+                statistic: worst_bias(&counts, config.avalanche_reps),
+                limit: bias_limit(1.0, config.avalanche_reps),
+                known: false,
+            }
+        })
+        .collect()
+}
+
+/// Bit independence criterion: flipping a key bit flips any two of the low
+/// 64 output bits together with probability one quarter. Uses SMHasher's
+/// 11-byte keys.
+pub(crate) fn bit_independence(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    const LEN: usize = 11;
+    let out_bits = candidate.bits.min(64);
+    let mut rng = Rng::new(2);
+    let mut key = [0u8; LEN];
+    let mut both = vec![0u32; out_bits * out_bits];
+    let mut worst = 0f64;
+    for bit in 0..LEN * 8 {
+        both.fill(0);
+        for _ in 0..config.bic_reps {
+            rng.fill(&mut key);
+            let base = (candidate.hash)(&key, 0)[0];
+            key[bit / 8] ^= 1 << (bit % 8);
+            let mut diff = base ^ (candidate.hash)(&key, 0)[0];
+            while diff != 0 {
+                let j = diff.trailing_zeros() as usize;
+                diff &= diff - 1;
+                let row = &mut both[j * out_bits..];
+                let mut rest = diff;
+                while rest != 0 {
+                    row[rest.trailing_zeros() as usize] += 1;
+                    rest &= rest - 1;
+                }
+            }
+        }
+        for j in 0..out_bits {
+            for k in j + 1..out_bits {
+                let p = both[j * out_bits + k] as f64 / config.bic_reps as f64;
+                worst = worst.max((4.0 * p - 1.0).abs());
+            }
+        }
+    }
+    vec![Outcome {
+        battery: "bic",
+        case: format!("{LEN}-byte keys, bits 0..{out_bits}"),
+        statistic: worst,
+        // The standard deviation of 4p for p = 1/4 is 4 * sqrt(3/16) = sqrt(3).
+        limit: bias_limit(3f64.sqrt(), config.bic_reps),
+        known: false,
+    }]
+}
+
+/// Seed avalanche, flipping each used seed bit over keys of mixed lengths,
+/// and collisions of one key hashed under consecutive seeds.
+pub(crate) fn seed_independence(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    if candidate.seed_bits == 0 {
+        return Vec::new();
+    }
+    const LENGTHS: [usize; 8] = [0, 3, 8, 16, 31, 64, 200, 1000];
+    let mut rng = Rng::new(3);
+    let mut counts = vec![0u32; candidate.seed_bits * candidate.bits];
+    let mut key = vec![0u8; 1000];
+    for rep in 0..config.seed_reps {
+        let key = &mut key[..LENGTHS[rep % LENGTHS.len()]];
+        rng.fill(key);
+        let seed = rng.next_u64();
+        let base = (candidate.hash)(key, seed);
+        for (bit, row) in counts.chunks_exact_mut(candidate.bits).enumerate() {
+            count_flips(row, &base, &(candidate.hash)(key, seed ^ (1 << bit)));
+        }
+    }
+    let mut outcomes = vec![Outcome {
+        battery: "seed",
+        case: format!("avalanche of {} seed bits", candidate.seed_bits),
+        statistic: worst_bias(&counts, config.seed_reps),
+        limit: bias_limit(1.0, config.seed_reps),
+        known: false,
+    }];
+    for len in [0, 1, 16, 100] {
+        let key = vec![0u8; len];
+        let hashes: Vec<[u64; 4]> = (0..config.seed_keys as u64)
+            .map(|seed| (candidate.hash)(&key, seed))
+            .collect();
+        let case = format!("{len} zero bytes, {} seeds", config.seed_keys);
+        outcomes.push(collisions_of("seed", &case, candidate, &hashes));
+    }
+    outcomes
+}
@@ -0,0 +1,128 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! The public functions of `cith` under test.
+//!
+//! A 64-bit seed `s` becomes the pair `(s, !s)` of `city_hash64_with_seeds`
+//! and the 128-bit seed `s | !s << 64`, so flipping one seed bit flips one bit
+//! in each half; `city_hash256_crc_with_seed` takes only the low 32 bits.
+use crate::Candidate;
+use cith::Hash256;
+
+fn seed128(seed: u64) -> u128 {
+    seed as u128 | ((!seed as u128) << 64)
+}
+
+fn words128(hash: u128) -> [u64; 4] {
+    [hash as u64, (hash >> 64) as u64, 0, 0]
+}
+
+fn words256(hash: Hash256) -> [u64; 4] {
+    [
+        hash.lo as u64,
+        (hash.lo >> 64) as u64,
+        hash.hi as u64,
+        (hash.hi >> 64) as u64,
+    ]
+}
+
+/// Every candidate, in the order of the report.
+pub const CANDIDATES: &[Candidate] = &[
+    Candidate {
+        name: "city_hash32",
+        bits: 32,
+        seed_bits: 0,
+        hash: |bytes, _| [cith::city_hash32(bytes) as u64, 0, 0, 0],
+    },
+    Candidate {
+        name: "city_hash64",
+        bits: 64,
+        seed_bits: 0,
+        hash: |bytes, _| [cith::city_hash64(bytes), 0, 0, 0],
+    },
+    Candidate {
+        name: "city_hash64_with_seed",
+        bits: 64,
+        seed_bits: 64,
+        hash: |bytes, seed| [cith::city_hash64_with_seed(bytes, seed), 0, 0, 0],
+    },
+    Candidate {
+        name: "city_hash64_with_seeds",
+        bits: 64,
+        seed_bits: 64,
+        hash: |bytes, seed| [cith::city_hash64_with_seeds(bytes, seed, !seed), 0, 0, 0],
+    },
+    Candidate {
+        name: "city_hash128",
+        bits: 128,
+        seed_bits: 0,
+        hash: |bytes, _| words128(cith::city_hash128(bytes)),
+    },
+    Candidate {
+        name: "city_hash128_with_seed",
+        bits: 128,
+        seed_bits: 64,
+        hash: |bytes, seed| words128(cith::city_hash128_with_seed(bytes, seed128(seed))),
+    },
+    Candidate {
+        name: "city_murmur",
+        bits: 128,
+        seed_bits: 0,
+        hash: |bytes, _| words128(cith::city_murmur(bytes)),
+    },
+    Candidate {
+        name: "city_murmur_with_seed",
+        bits: 128,
+        seed_bits: 64,
+        hash: |bytes, seed| words128(cith::city_murmur_with_seed(bytes, seed128(seed))),
+    },
+    Candidate {
+        name: "city_hash128_crc",
+        bits: 128,
+        seed_bits: 0,
+        hash: |bytes, _| words128(cith::city_hash128_crc(bytes)),
+    },
+    Candidate {
+        name: "city_hash128_crc_with_seed",
+        bits: 128,
+        seed_bits: 64,
+        hash: |bytes, seed| words128(cith::city_hash128_crc_with_seed(bytes, seed128(seed))),
+    },
+    Candidate {
+        name: "city_hash256_crc",
+        bits: 256,
+        seed_bits: 0,
+        hash: |bytes, _| words256(cith::city_hash256_crc(bytes)),
+    },
+    Candidate {
+        name: "city_hash256_crc_with_seed",
+        bits: 256,
+        seed_bits: 32,
+        hash: |bytes, seed| words256(cith::city_hash256_crc_with_seed(bytes, seed as u32)),
+    },
+];
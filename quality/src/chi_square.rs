@@ -0,0 +1,94 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Bucket distribution over every window of output bits.
+use crate::{Candidate, Config, Outcome, Rng, SIGMAS};
+
+/// `width` bits of the output starting at bit `offset`.
+fn window(hash: &[u64; 4], offset: usize, width: usize) -> usize {
+    let word = offset / 64;
+    let pair = hash[word] as u128 | ((*hash.get(word + 1).unwrap_or(&0) as u128) << 64);
+    ((pair >> (offset % 64)) as usize) & ((1 << width) - 1)
+}
+
+/// Worst normalized chi-square statistic `(X² - df) / sqrt(2 df)` over every
+/// window of `width` bits, and the offset of that window.
+fn worst_window(hashes: &[[u64; 4]], bits: usize, width: usize) -> (f64, usize) {
+    let buckets = 1usize << width;
+    let expected = hashes.len() as f64 / buckets as f64;
+    let df = (buckets - 1) as f64;
+    let mut counts = vec![0u32; buckets];
+    let mut worst = (f64::MIN, 0);
+    for offset in 0..=bits - width {
+        counts.fill(0);
+        for hash in hashes {
+            counts[window(hash, offset, width)] += 1;
+        }
+        let chi2: f64 = counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+        let z = (chi2 - df) / (2.0 * df).sqrt();
+        if z > worst.0 {
+            worst = (z, offset);
+        }
+    }
+    worst
+}
+
+/// Sequential integers, text keys and random keys spread over buckets taken
+/// from every window of output bits; at least 16 keys land in each bucket.
+pub(crate) fn buckets(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    let n = config.chi_square_keys as u64;
+    let width = ((n / 16).ilog2() as usize).min(16);
+    let mut rng = Rng::new(5);
+    let mut random = [0u8; 16];
+    ["sequential u64", "text keys", "random 16 bytes"]
+        .into_iter()
+        .map(|name| {
+            let hashes: Vec<[u64; 4]> = (0..n)
+                .map(|i| match name {
+                    "sequential u64" => (candidate.hash)(&i.to_le_bytes(), 0),
+                    "text keys" => (candidate.hash)(format!("key{i}").as_bytes(), 0),
+                    _ => {
+                        rng.fill(&mut random);
+                        (candidate.hash)(&random, 0)
+                    }
+                })
+                .collect();
+            let (z, offset) = worst_window(&hashes, candidate.bits, width);
+            Outcome {
+                battery: "chi-square",
+                case: format!("{name}, bits {offset}..{}", offset + width),
+                statistic: z,
+                limit: SIGMAS,
+                known: false,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,163 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Collision batteries over structured key sets.
+use crate::{Candidate, Config, Outcome, Rng, collisions_of};
+
+/// Hashes every key that `keys` produces with seed 0, then counts collisions.
+fn collisions(
+    battery: &'static str,
+    case: &str,
+    candidate: &Candidate,
+    keys: impl FnOnce(&mut dyn FnMut(&[u8])),
+) -> Outcome {
+    let mut hashes = Vec::new();
+    keys(&mut |key| hashes.push((candidate.hash)(key, 0)));
+    let case = format!("{case}, {} keys", hashes.len());
+    collisions_of(battery, &case, candidate, &hashes)
+}
+
+/// Visits `key` with every combination of at most `left` more bits set at or
+/// after `start`.
+fn sparse_keys(key: &mut [u8], start: usize, left: usize, visit: &mut dyn FnMut(&[u8])) {
+    visit(key);
+    if left == 0 {
+        return;
+    }
+    for bit in start..key.len() * 8 {
+        key[bit / 8] ^= 1 << (bit % 8);
+        sparse_keys(key, bit + 1, left - 1, visit);
+        key[bit / 8] ^= 1 << (bit % 8);
+    }
+}
+
+/// Keys that are all zero except for a few set bits.
+pub(crate) fn sparse(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    config
+        .sparse
+        .iter()
+        .map(|&(len, bits)| {
+            let case = format!("{len}-byte keys, up to {bits} bits");
+            collisions("sparse", &case, candidate, |visit| {
+                sparse_keys(&mut vec![0u8; len], 0, bits, visit)
+            })
+        })
+        .collect()
+}
+
+/// Keys made of a random block repeated eight times. Blocks are at least 8
+/// bytes, so the keys themselves practically never repeat.
+pub(crate) fn cyclic(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    const REPEATS: usize = 8;
+    let mut rng = Rng::new(4);
+    [8, 9, 12, 16, 17, 32]
+        .into_iter()
+        .map(|cycle| {
+            let case = format!("{cycle}-byte cycle");
+            collisions("cyclic", &case, candidate, |visit| {
+                let mut key = vec![0u8; cycle * REPEATS];
+                for _ in 0..config.cyclic_keys {
+                    rng.fill(&mut key[..cycle]);
+                    for i in 1..REPEATS {
+                        key.copy_within(..cycle, i * cycle);
+                    }
+                    visit(&key);
+                }
+            })
+        })
+        .collect()
+}
+
+/// All keys of two bytes up to the configured length with at most two
+/// non-zero bytes.
+pub(crate) fn two_bytes(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    let max_len = config.two_bytes_max_len;
+    let case = format!("lengths 2..={max_len}");
+    vec![collisions("two-bytes", &case, candidate, |visit| {
+        for len in 2..=max_len {
+            let mut key = vec![0u8; len];
+            visit(&key);
+            for i in 0..len {
+                for a in 1..=255 {
+                    key[i] = a;
+                    visit(&key);
+                    for j in i + 1..len {
+                        for b in 1..=255 {
+                            key[j] = b;
+                            visit(&key);
+                        }
+                        key[j] = 0;
+                    }
+                }
+                key[i] = 0;
+            }
+        }
+    })]
+}
+
+/// Visits every sequence of one to `max` blocks drawn from `blocks`.
+fn combinations(blocks: &[u32], max: usize, visit: &mut dyn FnMut(&[u8])) {
+    let mut digits = Vec::with_capacity(max);
+    let mut key = Vec::with_capacity(max * 4);
+    for count in 1..=max {
+        digits.clear();
+        digits.resize(count, 0usize);
+        loop {
+            key.clear();
+            key.extend(digits.iter().flat_map(|&d| blocks[d].to_le_bytes()));
+            visit(&key);
+            // Advance the digits like an odometer.
+            let Some(i) = digits.iter().rposition(|&d| d + 1 < blocks.len()) else {
+                break;
+            };
+            digits[i] += 1;
+            digits[i + 1..].fill(0);
+        }
+    }
+}
+
+/// SMHasher's combination keys: every sequence of a few 4-byte blocks that
+/// differ only in their low bits, their high bits, or the top bit.
+pub(crate) fn permutation(candidate: &Candidate, config: &Config) -> Vec<Outcome> {
+    let max = config.permutation_blocks;
+    let low: Vec<u32> = (0..8).collect();
+    let high: Vec<u32> = (0..8).map(|i| i << 29).collect();
+    [
+        ("low 3 bits", low, max),
+        ("high 3 bits", high, max),
+        ("0 and 0x80000000", vec![0, 0x8000_0000], max * 3),
+    ]
+    .into_iter()
+    .map(|(name, blocks, max)| {
+        let case = format!("{name}, up to {max} blocks");
+        collisions("permutation", &case, candidate, |visit| {
+            combinations(&blocks, max, visit)
+        })
+    })
+    .collect()
+}
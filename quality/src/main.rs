@@ -0,0 +1,96 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Prints the quality report.
+//!
+//! `cargo run --release -p cith-quality -- [--quick] [--candidate NAME]
+//! [--battery NAME]` runs the batteries on every candidate whose name contains
+//! `NAME`, or only the named battery. It exits with 1 if a case fails that is
+//! not one of the known weaknesses.
+use cith_quality::{BATTERIES, CANDIDATES, Config};
+use std::process::ExitCode;
+use std::time::Instant;
+
+fn main() -> ExitCode {
+    let mut config = Config::standard();
+    let mut candidate_filter = String::new();
+    let mut battery_filter = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--quick" => config = Config::quick(),
+            "--candidate" => candidate_filter = args.next().unwrap_or_default(),
+            "--battery" => battery_filter = args.next(),
+            _ => {
+                eprintln!("usage: cith-quality [--quick] [--candidate NAME] [--battery NAME]");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    let batteries: Vec<_> = BATTERIES
+        .iter()
+        .filter(|b| battery_filter.as_deref().is_none_or(|name| b.name == name))
+        .collect();
+    if batteries.is_empty() {
+        let names: Vec<_> = BATTERIES.iter().map(|b| b.name).collect();
+        eprintln!("unknown battery, expected one of {}", names.join(", "));
+        return ExitCode::from(2);
+    }
+
+    println!("backend: {}", cith::backend().name());
+    let mut failures = 0;
+    let mut known = 0;
+    for candidate in CANDIDATES
+        .iter()
+        .filter(|c| c.name.contains(&candidate_filter))
+    {
+        println!();
+        println!("{}", candidate.name);
+        println!(
+            "{:<18} {:<56} {:>12} {:>12}",
+            "battery", "case", "statistic", "limit"
+        );
+        for battery in &batteries {
+            let start = Instant::now();
+            for outcome in battery.run(candidate, &config) {
+                failures += usize::from(outcome.regressed());
+                known += usize::from(!outcome.passed() && outcome.known);
+                println!("{outcome}");
+            }
+            eprintln!("  {} took {:.1?}", battery.name, start.elapsed());
+        }
+    }
+    println!();
+    if failures == 0 {
+        println!("no regressions, {known} known weaknesses");
+        ExitCode::SUCCESS
+    } else {
+        println!("{failures} cases failed, {known} known weaknesses");
+        ExitCode::FAILURE
+    }
+}
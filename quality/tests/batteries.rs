@@ -0,0 +1,73 @@
+//! Runs the quick configuration on every candidate, and checks that the
+//! batteries do catch broken mixing.
+use cith_quality::{CANDIDATES, Candidate, Config, run};
+use std::thread;
+
+#[test]
+fn test_no_regressions() {
+    let config = Config::quick();
+    let failures: Vec<String> = thread::scope(|scope| {
+        let handles: Vec<_> = CANDIDATES
+            .iter()
+            .map(|candidate| scope.spawn(|| (candidate.name, run(candidate, &config))))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                let (name, outcomes) = handle.join().unwrap();
+                outcomes
+                    .into_iter()
+                    .filter(|outcome| outcome.regressed())
+                    .map(move |outcome| format!("{name}: {outcome}"))
+            })
+            .collect()
+    });
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn failed_batteries(candidate: &Candidate) -> Vec<&'static str> {
+    let mut batteries: Vec<_> = run(candidate, &Config::quick())
+        .into_iter()
+        .filter(|outcome| outcome.regressed())
+        .map(|outcome| outcome.battery)
+        .collect();
+    batteries.dedup();
+    batteries
+}
+
+#[test]
+fn test_catches_byte_sum() {
+    let byte_sum = Candidate {
+        name: "byte sum",
+        bits: 64,
+        seed_bits: 0,
+        hash: |bytes, _| [bytes.iter().map(|&b| b as u64).sum(), 0, 0, 0],
+    };
+    let failed = failed_batteries(&byte_sum);
+    for battery in ["avalanche", "bic", "sparse", "permutation", "chi-square"] {
+        assert!(failed.contains(&battery), "{battery} passed: {failed:?}");
+    }
+}
+
+#[test]
+fn test_catches_missing_shift_mix() {
+    // Multiply-xor over 64-bit words with no shift_mix between rounds, so low
+    // output bits never see the high bits of the key.
+    let unmixed = Candidate {
+        name: "no shift_mix",
+        bits: 64,
+        seed_bits: 0,
+        hash: |bytes, _| {
+            let h = bytes.chunks(8).fold(bytes.len() as u64, |h, chunk| {
+                let mut word = [0u8; 8];
+                word[..chunk.len()].copy_from_slice(chunk);
+                (h ^ u64::from_le_bytes(word)).wrapping_mul(0x9ae1_6a3b_2f90_404f)
+            });
+            [h, 0, 0, 0]
+        },
+    };
+    let failed = failed_batteries(&unmixed);
+    for battery in ["avalanche", "bic", "chi-square"] {
+        assert!(failed.contains(&battery), "{battery} passed: {failed:?}");
+    }
+}
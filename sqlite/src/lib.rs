@@ -0,0 +1,133 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! CityHash SQL functions for SQLite.
+//!
+//! [`register_functions`] adds the functions below to a `rusqlite`
+//! connection; with the `loadable_extension` feature the cdylib is also a
+//! loadable extension (`.load ./libcith_sqlite`) that registers them.
+//!
+//! | Function | Result |
+//! |---|---|
+//! | `cityhash32(x)` | `city_hash32`, an INTEGER in `0..2^32` |
+//! | `cityhash64(x)` | `city_hash64` |
+//! | `cityhash64_seed(x, seed)` | `city_hash64_with_seed` |
+//! | `cityhash128_hex(x)` | `city_hash128` as 32 lowercase hex digits, high half first |
+//!
+//! SQLite integers are signed, so the 64-bit results are the two's-complement
+//! reinterpretation of the `u64` hash (`hash as i64`), and `seed` is taken the
+//! same way (`seed as u64`).
+//!
+//! The argument `x` is hashed as follows:
+//! - BLOB: its bytes;
+//! - TEXT: its UTF-8 bytes, so `cityhash64('abc')` equals `cityhash64(x'616263')`;
+//! - INTEGER: the 8 little-endian bytes of the `i64`;
+//! - REAL: the 8 little-endian bytes of the `f64`;
+//! - NULL: no hash, the result is NULL.
+//!
+//! A NULL `seed` also gives NULL, and a seed that is not an INTEGER is an error.
+use rusqlite::functions::{Context, FunctionFlags};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, Error, Result};
+
+/// Calls `f` with the bytes of argument `idx`, or returns `None` for NULL.
+fn with_arg<R>(ctx: &Context<'_>, idx: usize, f: impl FnOnce(&[u8]) -> R) -> Option<R> {
+    match ctx.get_raw(idx) {
+        ValueRef::Null => None,
+        ValueRef::Integer(v) => Some(f(&v.to_le_bytes())),
+        ValueRef::Real(v) => Some(f(&v.to_le_bytes())),
+        ValueRef::Text(bytes) | ValueRef::Blob(bytes) => Some(f(bytes)),
+    }
+}
+
+/// Reads argument `idx` as a 64-bit seed, `None` for NULL.
+fn seed_arg(ctx: &Context<'_>, idx: usize) -> Result<Option<u64>> {
+    match ctx.get_raw(idx) {
+        ValueRef::Null => Ok(None),
+        ValueRef::Integer(v) => Ok(Some(v as u64)),
+        other => Err(Error::UserFunctionError(
+            format!(
+                "cityhash64_seed: seed must be an INTEGER, got {}",
+                other.data_type()
+            )
+            .into(),
+        )),
+    }
+}
+
+/// Registers `cityhash32`, `cityhash64`, `cityhash64_seed` and
+/// `cityhash128_hex` on `conn`.
+///
+/// The functions are deterministic and innocuous, so they may be used in
+/// indexes, generated columns and views.
+pub fn register_functions(conn: &Connection) -> Result<()> {
+    let flags = FunctionFlags::SQLITE_UTF8
+        | FunctionFlags::SQLITE_DETERMINISTIC
+        | FunctionFlags::SQLITE_INNOCUOUS;
+    conn.create_scalar_function("cityhash32", 1, flags, |ctx| {
+        Ok(with_arg(ctx, 0, |bytes| cith::city_hash32(bytes) as i64))
+    })?;
+    conn.create_scalar_function("cityhash64", 1, flags, |ctx| {
+        Ok(with_arg(ctx, 0, |bytes| cith::city_hash64(bytes) as i64))
+    })?;
+    conn.create_scalar_function("cityhash64_seed", 2, flags, |ctx| {
+        let Some(seed) = seed_arg(ctx, 1)? else {
+            return Ok(None);
+        };
+        Ok(with_arg(ctx, 0, |bytes| {
+            cith::city_hash64_with_seed(bytes, seed) as i64
+        }))
+    })?;
+    conn.create_scalar_function("cityhash128_hex", 1, flags, |ctx| {
+        Ok(with_arg(ctx, 0, |bytes| {
+            format!("{:032x}", cith::city_hash128(bytes))
+        }))
+    })?;
+    Ok(())
+}
+
+/// Entry point of the loadable extension.
+///
+/// # Safety
+///
+/// Must only be called by SQLite while loading the extension.
+#[cfg(feature = "loadable_extension")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sqlite3_extension_init(
+    db: *mut rusqlite::ffi::sqlite3,
+    pz_err_msg: *mut *mut std::ffi::c_char,
+    p_api: *mut rusqlite::ffi::sqlite3_api_routines,
+) -> std::ffi::c_int {
+    unsafe { Connection::extension_init2(db, pz_err_msg, p_api, extension_init) }
+}
+
+#[cfg(feature = "loadable_extension")]
+fn extension_init(conn: Connection) -> Result<bool> {
+    register_functions(&conn)?;
+    Ok(false)
+}
@@ -0,0 +1,104 @@
+use cith_sqlite::register_functions;
+use rusqlite::types::Value;
+use rusqlite::{Connection, params};
+
+fn connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    register_functions(&conn).unwrap();
+    conn
+}
+
+#[test]
+fn test_matches_crate() {
+    let conn = connection();
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let (h32, h64, h64s, h128): (i64, i64, i64, String) = conn
+        .query_row(
+            "SELECT cityhash32(?1), cityhash64(?1), cityhash64_seed(?1, ?2), cityhash128_hex(?1)",
+            params![&data[..], 42i64],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(h32, cith::city_hash32(data) as i64);
+    assert_eq!(h64 as u64, cith::city_hash64(data));
+    assert_eq!(h64s as u64, cith::city_hash64_with_seed(data, 42));
+    assert_eq!(h128, format!("{:032x}", cith::city_hash128(data)));
+}
+
+#[test]
+fn test_text_equals_blob() {
+    let conn = connection();
+    let (text, blob): (i64, i64) = conn
+        .query_row(
+            "SELECT cityhash64('Grüße'), cityhash64(CAST('Grüße' AS BLOB))",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(text, blob);
+    assert_eq!(text as u64, cith::city_hash64("Grüße".as_bytes()));
+}
+
+#[test]
+fn test_numbers() {
+    let conn = connection();
+    let (int, real): (i64, i64) = conn
+        .query_row("SELECT cityhash64(-5), cityhash64(1.5)", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(int as u64, cith::city_hash64(&(-5i64).to_le_bytes()));
+    assert_eq!(real as u64, cith::city_hash64(&1.5f64.to_le_bytes()));
+}
+
+#[test]
+fn test_null() {
+    let conn = connection();
+    let values: Vec<Value> = conn
+        .query_row(
+            "SELECT cityhash32(NULL), cityhash64(NULL), cityhash64_seed(NULL, 1), \
+             cityhash128_hex(NULL), cityhash64_seed('abc', NULL)",
+            [],
+            |row| (0..5).map(|i| row.get(i)).collect(),
+        )
+        .unwrap();
+    assert!(values.iter().all(|v| *v == Value::Null), "{values:?}");
+}
+
+#[test]
+fn test_negative_seed() {
+    let conn = connection();
+    let hash: i64 = conn
+        .query_row("SELECT cityhash64_seed('abc', -1)", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(hash as u64, cith::city_hash64_with_seed(b"abc", u64::MAX));
+}
+
+#[test]
+fn test_bad_seed() {
+    let conn = connection();
+    let result: rusqlite::Result<i64> =
+        conn.query_row("SELECT cityhash64_seed('abc', 'seed')", [], |row| {
+            row.get(0)
+        });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_deterministic_index() {
+    let conn = connection();
+    conn.execute_batch(
+        "CREATE TABLE t(v BLOB);
+         CREATE INDEX t_hash ON t(cityhash64(v));
+         INSERT INTO t VALUES (x'00'), ('abc'), (NULL);",
+    )
+    .unwrap();
+    let count: i64 = conn
+        .query_row(
+            "SELECT count(*) FROM t WHERE cityhash64(v) = ?1",
+            params![cith::city_hash64(b"abc") as i64],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(count, 1);
+}
@@ -0,0 +1,52 @@
+//! Builds the cdylib with the `loadable_extension` feature and loads it into a
+//! plain connection, as `.load ./libcith_sqlite` does. The feature reroutes
+//! rusqlite through the extension API, so it cannot be enabled in this test
+//! binary; cargo builds the extension in a target directory of its own.
+use rusqlite::{Connection, LoadExtensionGuard, params};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::PathBuf;
+use std::process::Command;
+
+fn build_extension() -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("../target/loadable_extension");
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "-p",
+            "cith-sqlite",
+            "--features",
+            "loadable_extension",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .current_dir(&manifest_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the extension failed");
+    target_dir
+        .join("debug")
+        .join(format!("{DLL_PREFIX}cith_sqlite{DLL_SUFFIX}"))
+}
+
+#[test]
+fn test_load_extension() {
+    let path = build_extension();
+    let conn = Connection::open_in_memory().unwrap();
+    unsafe {
+        let _guard = LoadExtensionGuard::new(&conn).unwrap();
+        conn.load_extension(&path, None::<&str>).unwrap();
+    }
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let (h32, h64, h64s, h128): (i64, i64, i64, String) = conn
+        .query_row(
+            "SELECT cityhash32(?1), cityhash64(?1), cityhash64_seed(?1, ?2), cityhash128_hex(?1)",
+            params![&data[..], 42i64],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(h32, cith::city_hash32(data) as i64);
+    assert_eq!(h64 as u64, cith::city_hash64(data));
+    assert_eq!(h64s as u64, cith::city_hash64_with_seed(data, 42));
+    assert_eq!(h128, format!("{:032x}", cith::city_hash128(data)));
+}
@@ -0,0 +1,597 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! A HashDoS-resistant map that hashes with CityHash until it is attacked.
+//!
+//! A random seed does not protect a map keyed by [`city_hash64_with_seed`]:
+//! the seeded hash is a function of the unseeded [`city_hash64`](crate::city_hash64)
+//! of the key, so every collision of CityHash64 collides under all seeds, and
+//! such collisions are cheap to compute. A public-facing map flooded with them
+//! degrades to linear probing on every lookup.
+//!
+//! [`AdaptiveHashMap`] keeps CityHash's speed for ordinary keys and counts
+//! how many of its keys fall in each bucket of a table as large as the map. A
+//! uniform hash practically never puts [`SKEW_LIMIT`] keys in one bucket.
+//! When a bucket reaches that count the map is rehashed once with a keyed
+//! SipHash-1-3 ([`RandomState`]) and stays keyed for the rest of its life.
+//!
+//! [`AdaptiveState`] is the [`BuildHasher`] it uses. On its own, in a plain
+//! [`HashMap`], it never escalates.
+use crate::city64::city_hash64_with_seed;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::{self, DefaultHasher, RandomState};
+use std::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+
+/// Keys in one bucket at which an [`AdaptiveHashMap`] switches to SipHash.
+///
+/// With one bucket per key, a uniform hash fills a bucket this far with a
+/// probability below 10<sup>-35</sup>.
+pub const SKEW_LIMIT: u8 = 32;
+
+/// The smallest bucket table an [`AdaptiveHashMap`] tracks.
+const MIN_BUCKETS: usize = 64;
+
+/// A [`BuildHasher`] for CityHash64 with a random seed, or for keyed SipHash.
+///
+/// [`AdaptiveState::new`] hashes with [`city_hash64_with_seed`] and a seed
+/// drawn from [`RandomState`]; [`AdaptiveState::keyed`] hashes with SipHash-1-3
+/// and random keys.
+#[derive(Clone)]
+pub struct AdaptiveState {
+    mode: Mode,
+}
+
+#[derive(Clone)]
+enum Mode {
+    City(u64),
+    Keyed(RandomState),
+}
+
+impl AdaptiveState {
+    /// Creates a state hashing with CityHash64 and a random seed.
+    pub fn new() -> AdaptiveState {
+        AdaptiveState::with_seed(RandomState::new().hash_one(0u64))
+    }
+
+    /// Creates a state hashing with CityHash64 and `seed`.
+    ///
+    /// A fixed seed makes hashes reproducible, and also predictable.
+    pub fn with_seed(seed: u64) -> AdaptiveState {
+        AdaptiveState {
+            mode: Mode::City(seed),
+        }
+    }
+
+    /// Creates a state hashing with SipHash-1-3 and random keys.
+    pub fn keyed() -> AdaptiveState {
+        AdaptiveState {
+            mode: Mode::Keyed(RandomState::new()),
+        }
+    }
+
+    /// Whether the state hashes with SipHash rather than CityHash.
+    pub fn is_keyed(&self) -> bool {
+        matches!(self.mode, Mode::Keyed(_))
+    }
+}
+
+impl Default for AdaptiveState {
+    fn default() -> Self {
+        AdaptiveState::new()
+    }
+}
+
+impl fmt::Debug for AdaptiveState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdaptiveState")
+            .field("keyed", &self.is_keyed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl BuildHasher for AdaptiveState {
+    type Hasher = AdaptiveHasher;
+
+    fn build_hasher(&self) -> AdaptiveHasher {
+        match &self.mode {
+            Mode::City(seed) => AdaptiveHasher(Inner::City {
+                bytes: Vec::new(),
+                seed: *seed,
+            }),
+            Mode::Keyed(state) => AdaptiveHasher(Inner::Keyed(state.build_hasher())),
+        }
+    }
+}
+
+/// The [`Hasher`] of an [`AdaptiveState`].
+pub struct AdaptiveHasher(Inner);
+
+enum Inner {
+    /// CityHash64 of the written bytes with the seed of the state.
+    City { bytes: Vec<u8>, seed: u64 },
+    /// SipHash-1-3 with the keys of the state.
+    Keyed(DefaultHasher),
+}
+
+impl Hasher for AdaptiveHasher {
+    fn finish(&self) -> u64 {
+        match &self.0 {
+            Inner::City { bytes, seed } => city_hash64_with_seed(bytes, *seed),
+            Inner::Keyed(hasher) => hasher.finish(),
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        match &mut self.0 {
+            Inner::City { bytes, .. } => bytes.extend_from_slice(data),
+            Inner::Keyed(hasher) => hasher.write(data),
+        }
+    }
+}
+
+/// A [`HashMap`] that hashes with CityHash64 until its buckets are skewed,
+/// then rehashes itself with keyed SipHash.
+///
+/// See the [module documentation](self). While the map still hashes with
+/// CityHash, inserting and removing a key hashes it twice: once to count it
+/// in its bucket and once in the map.
+pub struct AdaptiveHashMap<K, V> {
+    map: HashMap<K, V, AdaptiveState>,
+    /// Keys per bucket, for a power-of-two number of buckets; empty once
+    /// the map is keyed.
+    buckets: Vec<u8>,
+}
+
+impl<K, V> AdaptiveHashMap<K, V> {
+    /// Creates an empty map hashing with CityHash64 and a random seed.
+    pub fn new() -> AdaptiveHashMap<K, V> {
+        AdaptiveHashMap::with_hasher(AdaptiveState::new())
+    }
+
+    /// Creates an empty map with room for `capacity` keys.
+    pub fn with_capacity(capacity: usize) -> AdaptiveHashMap<K, V> {
+        AdaptiveHashMap {
+            map: HashMap::with_capacity_and_hasher(capacity, AdaptiveState::new()),
+            buckets: vec![0; bucket_count(capacity)],
+        }
+    }
+
+    /// Creates an empty map using `state`.
+    ///
+    /// A map given a keyed state never changes its hasher.
+    pub fn with_hasher(state: AdaptiveState) -> AdaptiveHashMap<K, V> {
+        let buckets = if state.is_keyed() {
+            Vec::new()
+        } else {
+            vec![0; MIN_BUCKETS]
+        };
+        AdaptiveHashMap {
+            map: HashMap::with_hasher(state),
+            buckets,
+        }
+    }
+
+    /// Whether the map has switched to keyed SipHash.
+    pub fn is_keyed(&self) -> bool {
+        self.map.hasher().is_keyed()
+    }
+
+    /// The underlying map.
+    pub fn as_map(&self) -> &HashMap<K, V, AdaptiveState> {
+        &self.map
+    }
+
+    /// Consumes the wrapper, returning the underlying map.
+    pub fn into_map(self) -> HashMap<K, V, AdaptiveState> {
+        self.map
+    }
+
+    /// Number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the entries in arbitrary order.
+    pub fn iter(&self) -> hash_map::Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    /// Iterates over the keys in arbitrary order.
+    pub fn keys(&self) -> hash_map::Keys<'_, K, V> {
+        self.map.keys()
+    }
+
+    /// Iterates over the values in arbitrary order.
+    pub fn values(&self) -> hash_map::Values<'_, K, V> {
+        self.map.values()
+    }
+
+    /// Removes every entry, keeping the hasher.
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.buckets.fill(0);
+    }
+}
+
+impl<K: Eq + Hash, V> AdaptiveHashMap<K, V> {
+    /// Inserts `value` under `key`, returning the value it replaces.
+    ///
+    /// Rehashes the whole map with keyed SipHash when `key` is the
+    /// [`SKEW_LIMIT`]th key in its bucket.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if self.buckets.is_empty() {
+            return self.map.insert(key, value);
+        }
+        let hash = self.map.hasher().hash_one(&key);
+        let old = self.map.insert(key, value);
+        if old.is_none() {
+            if self.map.len() > self.buckets.len() {
+                self.grow();
+            } else {
+                let mask = self.buckets.len() - 1;
+                let count = &mut self.buckets[hash as usize & mask];
+                *count += 1;
+                if *count >= SKEW_LIMIT {
+                    self.escalate();
+                }
+            }
+        }
+        old
+    }
+
+    /// Returns a reference to the value under `key`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /// Returns a mutable reference to the value under `key`.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get_mut(key)
+    }
+
+    /// Whether the map has an entry under `key`.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Removes the entry under `key`, returning its value.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.map.remove(key);
+        if value.is_some() && !self.buckets.is_empty() {
+            let mask = self.buckets.len() - 1;
+            self.buckets[self.map.hasher().hash_one(key) as usize & mask] -= 1;
+        }
+        value
+    }
+
+    /// Doubles the bucket table and recounts every key into it.
+    fn grow(&mut self) {
+        self.buckets = vec![0; bucket_count(self.map.len())];
+        let mask = self.buckets.len() - 1;
+        let state = self.map.hasher();
+        let skewed = self.map.keys().any(|key| {
+            let count = &mut self.buckets[state.hash_one(key) as usize & mask];
+            *count = count.saturating_add(1);
+            *count >= SKEW_LIMIT
+        });
+        if skewed {
+            self.escalate();
+        }
+    }
+
+    /// Rehashes the map with keyed SipHash and stops counting buckets.
+    fn escalate(&mut self) {
+        let map = std::mem::replace(&mut self.map, HashMap::with_hasher(AdaptiveState::keyed()));
+        self.map.reserve(map.len());
+        self.map.extend(map);
+        self.buckets = Vec::new();
+    }
+}
+
+/// Buckets tracked for `len` keys: a power of two, at least one per key.
+fn bucket_count(len: usize) -> usize {
+    len.max(MIN_BUCKETS).next_power_of_two()
+}
+
+impl<K, V> Default for AdaptiveHashMap<K, V> {
+    fn default() -> Self {
+        AdaptiveHashMap::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for AdaptiveHashMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.map.iter()).finish()
+    }
+}
+
+impl<K: Eq + Hash, V> Extend<(K, V)> for AdaptiveHashMap<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Eq + Hash, V> FromIterator<(K, V)> for AdaptiveHashMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = AdaptiveHashMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a AdaptiveHashMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = hash_map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.iter()
+    }
+}
+
+impl<K, V> IntoIterator for AdaptiveHashMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = hash_map::IntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city_hash64;
+    use crate::city64::{K1, K2};
+
+    /// Multiplicative inverse of an odd `x` modulo 2<sup>64</sup>.
+    fn inverse(x: u64) -> u64 {
+        let mut y = x;
+        for _ in 0..5 {
+            y = y.wrapping_mul(2u64.wrapping_sub(x.wrapping_mul(y)));
+        }
+        y
+    }
+
+    /// Completes the first and last words of a 32-byte message to one whose
+    /// CityHash64 is the same for every `w0` and `w3`, for every seed.
+    ///
+    /// Between 17 and 32 bytes, CityHash64 finishes with `HashLen16(u, v)`
+    /// where, for the words `w0..w3` of the message,
+    /// `u = ror(a + b, 43) + ror(c, 30) + d` and `v = a + ror(b + k2, 18) + c`
+    /// with `a = w0 * k1`, `b = w1`, `c = w3 * mul` and `d = w2 * k2`. Fixing
+    /// `u` and `v` determines `b` from `a` and `c`, then `d`; `k2` is odd, so
+    /// `w2` follows. The seeded hash only mixes the seed into the unseeded
+    /// one, so the collisions hold for every seed.
+    fn complete(w0: u64, w3: u64) -> [u64; 4] {
+        const U: u64 = 0x0123_4567_89ab_cdef;
+        const V: u64 = 0xfedc_ba98_7654_3210;
+        let a = w0.wrapping_mul(K1);
+        let c = w3.wrapping_mul(K2.wrapping_add(64));
+        let b = V
+            .wrapping_sub(a)
+            .wrapping_sub(c)
+            .rotate_left(18)
+            .wrapping_sub(K2);
+        let d = U
+            .wrapping_sub(a.wrapping_add(b).rotate_right(43))
+            .wrapping_sub(c.rotate_right(30));
+        [w0, b, d.wrapping_mul(inverse(K2)), w3]
+    }
+
+    fn bytes(words: &[u64]) -> Vec<u8> {
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    /// Spreads `i` over the low 7 bits of each byte: an ASCII word.
+    fn ascii(i: u64) -> u64 {
+        (0..8).fold(0, |word, k| word | ((i >> (7 * k)) & 0x7f) << (8 * k))
+    }
+
+    fn is_ascii(word: u64) -> bool {
+        word & 0x8080_8080_8080_8080 == 0
+    }
+
+    /// `n` distinct 31-byte strings that collide as `HashMap` keys.
+    ///
+    /// `str` hashes its bytes followed by `0xff`, so the 32-byte message ends
+    /// in `0xff`. Of the completions of ASCII `w0` and `w3`, about one in
+    /// 2<sup>16</sup> is ASCII as well.
+    fn colliding_strings(n: usize) -> Vec<String> {
+        let w3 = ascii(0x0123_4567_89ab_cdef) & 0x00ff_ffff_ffff_ffff | 0xff << 56;
+        (0..)
+            .map(|i| complete(ascii(i), w3))
+            .filter(|words| is_ascii(words[1]) && is_ascii(words[2]))
+            .map(|words| {
+                let mut key = bytes(&words);
+                key.pop();
+                String::from_utf8(key).unwrap()
+            })
+            .take(n)
+            .collect()
+    }
+
+    /// `n` distinct 24-byte vectors that collide as `HashMap` keys.
+    ///
+    /// `[u8]` hashes its length as a `usize` before the bytes, which fixes
+    /// `w0`; every `w3` then has a completion.
+    #[cfg(target_pointer_width = "64")]
+    fn colliding_vectors(n: u64) -> Vec<Vec<u8>> {
+        let w0 = u64::from_le_bytes(24usize.to_ne_bytes());
+        (0..n)
+            .map(|i| bytes(&complete(w0, i.wrapping_mul(K1))[1..]))
+            .collect()
+    }
+
+    /// Checks that `keys` all hash alike as keys, for several seeds.
+    fn assert_collide<K: Hash>(keys: &[K]) {
+        for seed in [0, 1, 0x5eed, u64::MAX] {
+            let state = AdaptiveState::with_seed(seed);
+            let hash = state.hash_one(&keys[0]);
+            for key in keys {
+                assert_eq!(state.hash_one(key), hash, "seed {seed}");
+            }
+        }
+    }
+
+    /// Inserts `keys`, which collide, and checks the map escalates at the
+    /// [`SKEW_LIMIT`]th and then spreads them.
+    fn assert_escalates<K: Hash + Eq + Clone + fmt::Debug>(keys: &[K]) {
+        let mut map = AdaptiveHashMap::new();
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(key.clone(), i);
+            assert_eq!(map.is_keyed(), i + 1 >= SKEW_LIMIT as usize, "key {i}");
+        }
+        assert_eq!(map.len(), keys.len());
+        for (i, key) in keys.iter().enumerate() {
+            assert_eq!(map.get(key), Some(&i));
+        }
+        let state = map.as_map().hasher();
+        let mut hashes: Vec<u64> = keys.iter().map(|key| state.hash_one(key)).collect();
+        hashes.sort();
+        hashes.dedup();
+        assert_eq!(hashes.len(), keys.len());
+    }
+
+    #[test]
+    fn test_multicollision() {
+        let messages: Vec<Vec<u8>> = (0..1000u64)
+            .map(|i| bytes(&complete(i, i.wrapping_mul(K2))))
+            .collect();
+        let hash = city_hash64(&messages[0]);
+        for message in &messages {
+            assert_eq!(city_hash64(message), hash);
+        }
+        for seed in [0, 1, 0x5eed, u64::MAX] {
+            let seeded = city_hash64_with_seed(&messages[0], seed);
+            for message in &messages {
+                assert_eq!(city_hash64_with_seed(message, seed), seeded, "seed {seed}");
+            }
+        }
+        let mut distinct = messages.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), messages.len());
+    }
+
+    #[test]
+    fn test_escalates_on_strings() {
+        let keys = colliding_strings(100);
+        assert_collide(&keys);
+        assert_escalates(&keys);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_escalates_on_vectors() {
+        let keys = colliding_vectors(5000);
+        assert_collide(&keys);
+        assert_escalates(&keys);
+    }
+
+    #[test]
+    fn test_stays_city_on_ordinary_keys() {
+        let mut map = AdaptiveHashMap::new();
+        for i in 0..200_000u64 {
+            map.insert(i, i * 3);
+        }
+        for i in (0..200_000u64).step_by(2) {
+            assert_eq!(map.remove(&i), Some(i * 3));
+        }
+        map.extend((200_000..300_000u64).map(|i| (i, i * 3)));
+        map.extend((0..100_000u64).map(|i| (i << 32, 0)));
+        assert!(!map.is_keyed());
+        assert_eq!(map.get(&1), Some(&3));
+        assert_eq!(map.get(&2), None);
+
+        let mut words = AdaptiveHashMap::new();
+        words.extend((0..100_000u64).map(|i| (format!("user-{i}"), i)));
+        assert!(!words.is_keyed());
+        assert_eq!(words.get("user-77"), Some(&77));
+    }
+
+    #[test]
+    fn test_removal_uncounts() {
+        let keys = colliding_strings(SKEW_LIMIT as usize);
+        let mut map = AdaptiveHashMap::new();
+        for _ in 0..10 {
+            for key in &keys[1..] {
+                map.insert(key.clone(), ());
+            }
+            for key in &keys[1..] {
+                assert_eq!(map.remove(key.as_str()), Some(()));
+            }
+        }
+        assert!(!map.is_keyed());
+        map.extend(keys.iter().map(|key| (key.clone(), ())));
+        assert!(map.is_keyed());
+    }
+
+    #[test]
+    fn test_hasher() {
+        for seed in [0, 42] {
+            let state = AdaptiveState::with_seed(seed);
+            assert!(!state.is_keyed());
+            let mut hasher = state.build_hasher();
+            hasher.write(b"abc");
+            assert_eq!(hasher.finish(), city_hash64_with_seed(b"abc", seed));
+        }
+        let keyed = AdaptiveState::keyed();
+        assert!(keyed.is_keyed());
+        assert_eq!(keyed.hash_one(7u64), keyed.clone().hash_one(7u64));
+
+        let mut map: AdaptiveHashMap<u32, u32> = AdaptiveHashMap::with_hasher(keyed);
+        map.insert(1, 2);
+        assert_eq!(map.get(&1), Some(&2));
+        assert!(map.is_keyed());
+    }
+}
@@ -0,0 +1,411 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Vectorized CityHash64 of Apache Arrow arrays.
+//!
+//! [`hash_array`] hashes every row of a column with
+//! [`city_hash64_with_seed`], reading the offset and value buffers of the
+//! array directly. A row hashes as:
+//!
+//! * `Utf8`, `LargeUtf8`, `Utf8View`: the UTF-8 bytes of the string;
+//! * `Binary`, `LargeBinary`, `BinaryView`, `FixedSizeBinary`: the bytes;
+//! * integers, floats, decimals, dates, times, timestamps and durations: the
+//!   little-endian bytes of the value, so a float hashes its bit pattern and
+//!   `0.0` and `-0.0` differ;
+//! * intervals: the little-endian bytes of each field in order, e.g. months,
+//!   days and nanoseconds for `Interval(MonthDayNano)`;
+//! * `Boolean`: one byte, `0` or `1`;
+//! * `Dictionary`: as its value, regardless of the key type.
+//!
+//! [`hash_columns`] hashes whole rows of several columns of equal length.
+//! Starting from `h = seed`, each column is folded in left to right as
+//! `h = hash128_to_64(h as u128 | (c as u128) << 64)`, where `c` is the
+//! column's row hash from [`hash_array`], or [`NULL_HASH`] for a null.
+//! Swapping two columns therefore changes the row hash.
+use crate::city64::{city_hash64_with_seed, hash128_to_64};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    BinaryViewType, ByteArrayType, ByteViewType, Decimal256Type, GenericBinaryType,
+    GenericStringType, IntervalDayTimeType, IntervalMonthDayNanoType, StringViewType,
+};
+use arrow_array::{Array, GenericByteArray, GenericByteViewArray, UInt64Array};
+use arrow_buffer::{ArrowNativeType, ScalarBuffer};
+use arrow_schema::{ArrowError, DataType, IntervalUnit};
+
+/// The hash [`hash_columns`] folds in for a null value.
+pub const NULL_HASH: u64 = 0x2c6f_e96e_e78b_6955;
+
+/// Hashes every row of `array` with [`city_hash64_with_seed`].
+///
+/// The result has the validity of `array`: null rows stay null, with a zero
+/// in the value slot. Types other than the ones in the module documentation
+/// return [`ArrowError::NotYetImplemented`].
+pub fn hash_array(array: &dyn Array, seed: u64) -> Result<UInt64Array, ArrowError> {
+    let mut hashes = vec![0u64; array.len()];
+    hash_rows(array, seed, &mut hashes)?;
+    let nulls = array.logical_nulls();
+    if let Some(nulls) = &nulls {
+        for (hash, valid) in hashes.iter_mut().zip(nulls.iter()) {
+            if !valid {
+                *hash = 0;
+            }
+        }
+    }
+    Ok(UInt64Array::new(ScalarBuffer::from(hashes), nulls))
+}
+
+/// Hashes every row of `columns` together, folding the column hashes in
+/// order as described in the module documentation.
+///
+/// The result has no nulls. All columns must have the same length, otherwise
+/// [`ArrowError::InvalidArgumentError`] is returned; an empty `columns` is
+/// also an error, since the row count would be unknown.
+pub fn hash_columns(columns: &[&dyn Array], seed: u64) -> Result<UInt64Array, ArrowError> {
+    let Some(first) = columns.first() else {
+        return Err(ArrowError::InvalidArgumentError(
+            "hash_columns needs at least one column".to_string(),
+        ));
+    };
+    let len = first.len();
+    let mut hashes = vec![seed; len];
+    let mut column_hashes = vec![0u64; len];
+    for column in columns {
+        if column.len() != len {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "hash_columns got columns of {} and {} rows",
+                len,
+                column.len()
+            )));
+        }
+        hash_rows(*column, seed, &mut column_hashes)?;
+        if let Some(nulls) = column.logical_nulls() {
+            for (hash, valid) in column_hashes.iter_mut().zip(nulls.iter()) {
+                if !valid {
+                    *hash = NULL_HASH;
+                }
+            }
+        }
+        for (hash, &column_hash) in hashes.iter_mut().zip(column_hashes.iter()) {
+            *hash = hash128_to_64(*hash as u128 | ((column_hash as u128) << 64));
+        }
+    }
+    Ok(UInt64Array::from(hashes))
+}
+
+/// Writes the hash of every row of `array` to `out`, including null rows,
+/// whose hashes are meaningless and overwritten by the callers.
+fn hash_rows(array: &dyn Array, seed: u64, out: &mut [u64]) -> Result<(), ArrowError> {
+    match array.data_type() {
+        DataType::Null => {}
+        DataType::Boolean => {
+            let values = array.as_boolean().values();
+            for (hash, value) in out.iter_mut().zip(values.iter()) {
+                *hash = city_hash64_with_seed(&[value as u8], seed);
+            }
+        }
+        DataType::Utf8 => hash_offsets(array.as_bytes::<GenericStringType<i32>>(), seed, out),
+        DataType::LargeUtf8 => hash_offsets(array.as_bytes::<GenericStringType<i64>>(), seed, out),
+        DataType::Binary => hash_offsets(array.as_bytes::<GenericBinaryType<i32>>(), seed, out),
+        DataType::LargeBinary => {
+            hash_offsets(array.as_bytes::<GenericBinaryType<i64>>(), seed, out)
+        }
+        DataType::Utf8View => hash_views(array.as_byte_view::<StringViewType>(), seed, out),
+        DataType::BinaryView => hash_views(array.as_byte_view::<BinaryViewType>(), seed, out),
+        DataType::FixedSizeBinary(width) => {
+            let array = array.as_fixed_size_binary();
+            hash_chunks(array.value_data(), *width as usize, seed, out);
+        }
+        DataType::Dictionary(_, _) => {
+            let array = array.as_any_dictionary();
+            let values = array.values();
+            let mut value_hashes = vec![0u64; values.len()];
+            hash_rows(values.as_ref(), seed, &mut value_hashes)?;
+            for (hash, key) in out.iter_mut().zip(array.normalized_keys()) {
+                // Keys of null rows may point anywhere; their hash is unused.
+                *hash = value_hashes.get(key).copied().unwrap_or_default();
+            }
+        }
+        // Composite values: each field little-endian, in declaration order.
+        DataType::Decimal256(_, _) => {
+            let values = array.as_primitive::<Decimal256Type>().values();
+            for (hash, value) in out.iter_mut().zip(values.iter()) {
+                *hash = city_hash64_with_seed(&value.to_le_bytes(), seed);
+            }
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            let values = array.as_primitive::<IntervalDayTimeType>().values();
+            for (hash, value) in out.iter_mut().zip(values.iter()) {
+                let mut bytes = [0u8; 8];
+                bytes[..4].copy_from_slice(&value.days.to_le_bytes());
+                bytes[4..].copy_from_slice(&value.milliseconds.to_le_bytes());
+                *hash = city_hash64_with_seed(&bytes, seed);
+            }
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            let values = array.as_primitive::<IntervalMonthDayNanoType>().values();
+            for (hash, value) in out.iter_mut().zip(values.iter()) {
+                let mut bytes = [0u8; 16];
+                bytes[..4].copy_from_slice(&value.months.to_le_bytes());
+                bytes[4..8].copy_from_slice(&value.days.to_le_bytes());
+                bytes[8..].copy_from_slice(&value.nanoseconds.to_le_bytes());
+                *hash = city_hash64_with_seed(&bytes, seed);
+            }
+        }
+        data_type if data_type.is_primitive() => {
+            let width = data_type.primitive_width().unwrap_or_default();
+            let data = array.to_data();
+            let start = data.offset() * width;
+            let values = &data.buffers()[0].as_slice()[start..start + data.len() * width];
+            hash_chunks(values, width, seed, out);
+        }
+        data_type => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "hashing {data_type} arrays"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Hashes the values of a string or binary array between consecutive offsets.
+fn hash_offsets<T: ByteArrayType>(array: &GenericByteArray<T>, seed: u64, out: &mut [u64]) {
+    let values = array.values().as_slice();
+    for (hash, window) in out.iter_mut().zip(array.value_offsets().windows(2)) {
+        let (start, end) = (window[0].as_usize(), window[1].as_usize());
+        *hash = city_hash64_with_seed(&values[start..end], seed);
+    }
+}
+
+/// Hashes the values of a string or binary view array.
+fn hash_views<T: ByteViewType + ?Sized>(
+    array: &GenericByteViewArray<T>,
+    seed: u64,
+    out: &mut [u64],
+) {
+    for (i, hash) in out.iter_mut().enumerate() {
+        *hash = city_hash64_with_seed(array.value(i).as_ref(), seed);
+    }
+}
+
+/// Hashes `values` as consecutive little-endian values of `width` bytes.
+///
+/// Only for scalar values: on big-endian hosts each value is byte-reversed.
+fn hash_chunks(values: &[u8], width: usize, seed: u64, out: &mut [u64]) {
+    if width == 0 {
+        out.fill(city_hash64_with_seed(&[], seed));
+        return;
+    }
+    if cfg!(target_endian = "big") {
+        let mut value = vec![0u8; width];
+        for (hash, chunk) in out.iter_mut().zip(values.chunks_exact(width)) {
+            value.copy_from_slice(chunk);
+            value.reverse();
+            *hash = city_hash64_with_seed(&value, seed);
+        }
+    } else {
+        for (hash, chunk) in out.iter_mut().zip(values.chunks_exact(width)) {
+            *hash = city_hash64_with_seed(chunk, seed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{
+        BinaryArray, BooleanArray, Decimal256Array, DictionaryArray, FixedSizeBinaryArray,
+        Float64Array, Int64Array, IntervalDayTimeArray, IntervalMonthDayNanoArray,
+        LargeStringArray, StringArray, StringViewArray, UInt8Array,
+    };
+    use arrow_buffer::{IntervalDayTime, IntervalMonthDayNano, i256};
+
+    fn expected(values: &[Option<&[u8]>], seed: u64) -> Vec<Option<u64>> {
+        values
+            .iter()
+            .map(|v| v.map(|v| city_hash64_with_seed(v, seed)))
+            .collect()
+    }
+
+    #[test]
+    fn test_strings() {
+        let rows = [Some("abc"), None, Some(""), Some("The quick brown fox")];
+        let bytes: Vec<Option<&[u8]>> = rows.iter().map(|v| v.map(str::as_bytes)).collect();
+        let arrays: [&dyn Array; 4] = [
+            &StringArray::from(rows.to_vec()),
+            &LargeStringArray::from(rows.to_vec()),
+            &StringViewArray::from(rows.to_vec()),
+            &BinaryArray::from(bytes.clone()),
+        ];
+        for array in arrays {
+            let hashes = hash_array(array, 42).unwrap();
+            assert_eq!(hashes.iter().collect::<Vec<_>>(), expected(&bytes, 42));
+        }
+    }
+
+    #[test]
+    fn test_sliced() {
+        let strings = StringArray::from(vec!["a", "bb", "ccc", "dddd"]).slice(1, 2);
+        let hashes = hash_array(&strings, 0).unwrap();
+        assert_eq!(
+            hashes.values().to_vec(),
+            [
+                city_hash64_with_seed(b"bb", 0),
+                city_hash64_with_seed(b"ccc", 0)
+            ]
+        );
+
+        let ints = Int64Array::from(vec![1, 2, 3, 4]).slice(2, 2);
+        let hashes = hash_array(&ints, 7).unwrap();
+        assert_eq!(
+            hashes.values().to_vec(),
+            [
+                city_hash64_with_seed(&3i64.to_le_bytes(), 7),
+                city_hash64_with_seed(&4i64.to_le_bytes(), 7)
+            ]
+        );
+
+        let fixed = FixedSizeBinaryArray::try_from_iter([[1u8, 2], [3, 4], [5, 6]].into_iter())
+            .unwrap()
+            .slice(1, 2);
+        let hashes = hash_array(&fixed, 0).unwrap();
+        assert_eq!(
+            hashes.values().to_vec(),
+            [
+                city_hash64_with_seed(&[3, 4], 0),
+                city_hash64_with_seed(&[5, 6], 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_primitives() {
+        let floats = Float64Array::from(vec![Some(1.5), None, Some(-0.0)]);
+        let hashes = hash_array(&floats, 1).unwrap();
+        assert_eq!(
+            hashes.iter().collect::<Vec<_>>(),
+            [
+                Some(city_hash64_with_seed(&1.5f64.to_le_bytes(), 1)),
+                None,
+                Some(city_hash64_with_seed(&(-0.0f64).to_le_bytes(), 1)),
+            ]
+        );
+        assert_eq!(hashes.values()[1], 0);
+
+        let bytes = UInt8Array::from(vec![200]);
+        assert_eq!(
+            hash_array(&bytes, 0).unwrap().value(0),
+            city_hash64_with_seed(&[200], 0)
+        );
+
+        let bools = BooleanArray::from(vec![true, false]);
+        assert_eq!(
+            hash_array(&bools, 0).unwrap().values().to_vec(),
+            [
+                city_hash64_with_seed(&[1], 0),
+                city_hash64_with_seed(&[0], 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_composites() {
+        let decimals = Decimal256Array::from(vec![i256::from_i128(-2)]);
+        let mut minus_two = [0xffu8; 32];
+        minus_two[0] = 0xfe;
+        assert_eq!(
+            hash_array(&decimals, 3).unwrap().value(0),
+            city_hash64_with_seed(&minus_two, 3)
+        );
+
+        let day_time = IntervalDayTimeArray::from(vec![IntervalDayTime::new(3, -1)]);
+        assert_eq!(
+            hash_array(&day_time, 3).unwrap().value(0),
+            city_hash64_with_seed(&[3, 0, 0, 0, 0xff, 0xff, 0xff, 0xff], 3)
+        );
+
+        let nanos = IntervalMonthDayNanoArray::from(vec![IntervalMonthDayNano::new(1, 2, -3)]);
+        let mut bytes = [0xffu8; 16];
+        bytes[..8].copy_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0]);
+        bytes[8] = 0xfd;
+        assert_eq!(
+            hash_array(&nanos, 3).unwrap().value(0),
+            city_hash64_with_seed(&bytes, 3)
+        );
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let dictionary: DictionaryArray<Int32Type> = vec![Some("x"), None, Some("y"), Some("x")]
+            .into_iter()
+            .collect();
+        let plain = StringArray::from(vec![Some("x"), None, Some("y"), Some("x")]);
+        assert_eq!(
+            hash_array(&dictionary, 3).unwrap(),
+            hash_array(&plain, 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_columns() {
+        let ids = Int64Array::from(vec![Some(1), Some(2), None]);
+        let names = StringArray::from(vec![Some("a"), None, Some("c")]);
+        let hashes = hash_columns(&[&ids, &names], 5).unwrap();
+        assert_eq!(hashes.null_count(), 0);
+        let row = |id: Option<i64>, name: Option<&str>| {
+            let a = id.map_or(NULL_HASH, |v| city_hash64_with_seed(&v.to_le_bytes(), 5));
+            let b = name.map_or(NULL_HASH, |v| city_hash64_with_seed(v.as_bytes(), 5));
+            let h = hash128_to_64(5 | ((a as u128) << 64));
+            hash128_to_64(h as u128 | ((b as u128) << 64))
+        };
+        assert_eq!(
+            hashes.values().to_vec(),
+            [
+                row(Some(1), Some("a")),
+                row(Some(2), None),
+                row(None, Some("c"))
+            ]
+        );
+        let swapped = hash_columns(&[&names, &ids], 5).unwrap();
+        assert_ne!(swapped.value(0), hashes.value(0));
+    }
+
+    #[test]
+    fn test_errors() {
+        let a = Int64Array::from(vec![1, 2]);
+        let b = Int64Array::from(vec![1]);
+        assert!(hash_columns(&[&a, &b], 0).is_err());
+        assert!(hash_columns(&[], 0).is_err());
+        let list =
+            arrow_array::ListArray::from_iter_primitive::<Int32Type, _, _>([Some(vec![Some(1)])]);
+        assert!(matches!(
+            hash_array(&list, 0),
+            Err(ArrowError::NotYetImplemented(_))
+        ));
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Environment variable consulted when the backend is selected for the first time.
+///
+/// Accepts the same names as [`Backend::from_str`]. Unknown names and backends that
+/// are not available on the running CPU are ignored and automatic detection is used.
+pub const BACKEND_ENV_VAR: &str = "CITH_BACKEND";
+
+/// Implementation used for the CRC-based functions ([`crate::city_hash256_crc`],
+/// the long-input path of [`crate::city_hash128_crc`] and [`crate::crc32c`]).
+///
+/// All backends produce bit-identical results; they only differ in speed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Table-driven software CRC, available on every target.
+    Portable,
+    /// x86 SSE4.2 `crc32` instructions, with PCLMULQDQ folding for bulk CRC32C on x86_64.
+    /// On 32-bit x86 each 64-bit CRC step is done as two 32-bit instructions.
+    Sse42,
+    /// ARMv8 CRC32 extension, with PMULL folding for bulk CRC32C.
+    ArmCrc,
+}
+
+impl Backend {
+    /// Every backend known to this crate, whether or not it is available.
+    pub const ALL: [Backend; 3] = [Backend::Portable, Backend::Sse42, Backend::ArmCrc];
+
+    /// Short lowercase name of the backend, as accepted by [`BACKEND_ENV_VAR`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Portable => "portable",
+            Backend::Sse42 => "sse4.2",
+            Backend::ArmCrc => "arm-crc",
+        }
+    }
+
+    /// Returns `true` if this backend was compiled in and the running CPU supports it.
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Portable => true,
+            #[cfg(all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                not(feature = "portable")
+            ))]
+            Backend::Sse42 => std::arch::is_x86_feature_detected!("sse4.2"),
+            #[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
+            Backend::ArmCrc => std::arch::is_aarch64_feature_detected!("crc"),
+            _ => false,
+        }
+    }
+
+    /// Returns all backends usable on the running CPU, the portable one first.
+    pub fn available() -> Vec<Backend> {
+        Backend::ALL
+            .into_iter()
+            .filter(|backend| backend.is_available())
+            .collect()
+    }
+
+    /// Returns the fastest backend usable on the running CPU.
+    pub fn detect() -> Backend {
+        Backend::ALL
+            .into_iter()
+            .rev()
+            .find(|backend| backend.is_available())
+            .unwrap_or(Backend::Portable)
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Backend::Portable => 1,
+            Backend::Sse42 => 2,
+            Backend::ArmCrc => 3,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Backend> {
+        match value {
+            1 => Some(Backend::Portable),
+            2 => Some(Backend::Sse42),
+            3 => Some(Backend::ArmCrc),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Backend {
+    type Err = ParseBackendError;
+
+    /// Parses a backend name. Matching is case-insensitive; `portable`, `sse4.2`
+    /// (or `sse42`) and `arm-crc` (or `crc`) are accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "portable" | "scalar" | "generic" => Ok(Backend::Portable),
+            "sse4.2" | "sse42" => Ok(Backend::Sse42),
+            "arm-crc" | "armcrc" | "crc" => Ok(Backend::ArmCrc),
+            _ => Err(ParseBackendError),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown backend name.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseBackendError;
+
+impl Display for ParseBackendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("unknown backend name")
+    }
+}
+
+impl std::error::Error for ParseBackendError {}
+
+/// Error returned by [`force_backend`] when the requested backend cannot run here.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BackendUnavailable(pub Backend);
+
+impl Display for BackendUnavailable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "backend `{}` is not available on this CPU or build",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for BackendUnavailable {}
+
+// 0 means "not selected yet".
+static SELECTED: AtomicU8 = AtomicU8::new(0);
+
+fn select_initial() -> Backend {
+    std::env::var(BACKEND_ENV_VAR)
+        .ok()
+        .and_then(|name| name.parse::<Backend>().ok())
+        .filter(|backend| backend.is_available())
+        .unwrap_or_else(Backend::detect)
+}
+
+/// Returns the backend currently used by the CRC-based functions.
+///
+/// On first use the backend is taken from [`BACKEND_ENV_VAR`] if it names an
+/// available backend, otherwise the fastest available one is detected.
+#[inline]
+pub fn backend() -> Backend {
+    if let Some(backend) = Backend::from_u8(SELECTED.load(Ordering::Relaxed)) {
+        return backend;
+    }
+    let backend = select_initial();
+    match SELECTED.compare_exchange(0, backend.to_u8(), Ordering::Relaxed, Ordering::Relaxed) {
+        Ok(_) => backend,
+        Err(current) => Backend::from_u8(current).unwrap_or(backend),
+    }
+}
+
+/// Forces the CRC-based functions to use `backend` from now on, process-wide.
+///
+/// This is meant for benchmarks and cross-backend testing; outputs do not depend
+/// on the backend. Fails if the backend is not available on the running CPU.
+pub fn force_backend(backend: Backend) -> Result<(), BackendUnavailable> {
+    if !backend.is_available() {
+        return Err(BackendUnavailable(backend));
+    }
+    SELECTED.store(backend.to_u8(), Ordering::Relaxed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for backend in Backend::ALL {
+            assert_eq!(backend.name().parse::<Backend>(), Ok(backend));
+        }
+        assert_eq!("SSE42".parse::<Backend>(), Ok(Backend::Sse42));
+        assert_eq!("avx512".parse::<Backend>(), Err(ParseBackendError));
+    }
+
+    #[test]
+    fn test_availability() {
+        let available = Backend::available();
+        assert_eq!(available.first(), Some(&Backend::Portable));
+        assert!(available.contains(&Backend::detect()));
+        assert!(available.contains(&backend()));
+        for backend in Backend::ALL {
+            assert_eq!(backend.is_available(), available.contains(&backend));
+        }
+    }
+}
@@ -26,8 +26,10 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+use crate::backend::{Backend, backend};
 use crate::city64::{K0, K1, hash_len_16_u64, read_le64, weak_hash_len_32_with_seeds};
-use crate::{city_hash256_crc, city_murmur_with_seed};
+use crate::city256::city256_crc_with;
+use crate::{Hash256, city_hash256_crc, city_murmur_with_seed};
 
 #[derive(Copy, Clone, Default)]
 struct DeinterleavedU128 {
@@ -224,12 +226,7 @@ pub fn city_hash128(bytes: &[u8]) -> u128 {
 ///
 /// A `u128` value representing the 128-bit hash of the input.
 pub fn city_hash128_crc(bytes: &[u8]) -> u128 {
-    if bytes.len() <= 900 {
-        city_hash128(bytes)
-    } else {
-        let hash = city_hash256_crc(bytes);
-        hash.hi
-    }
+    city128_crc_with(backend(), bytes, None)
 }
 
 /// Computes a 128-bit CRC-based CityHash of the given byte slice with a seed.
@@ -248,25 +245,70 @@ pub fn city_hash128_crc(bytes: &[u8]) -> u128 {
 ///
 /// A `u128` value representing the 128-bit hash of the input, influenced by the seed.
 pub fn city_hash128_crc_with_seed(bytes: &[u8], seed: u128) -> u128 {
-    if bytes.len() <= 900 {
-        city_hash128_with_seed(bytes, seed)
-    } else {
-        let hash = city_hash256_crc(bytes);
-        let result_lo = (hash.lo & 0xffff_ffff_ffff_ffff) as u64;
-        let result_hi = (hash.lo >> 64) as u64;
+    city128_crc_with(backend(), bytes, Some(seed))
+}
 
-        let result1_lo = (hash.hi & 0xffff_ffff_ffff_ffff) as u64;
-        let result1_hi = (hash.hi >> 64) as u64;
+/// [`city_hash128_crc`], or [`city_hash128_crc_with_seed`] when `seed` is set,
+/// with inputs longer than 900 bytes hashed on the given backend.
+#[inline]
+pub(crate) fn city128_crc_with(backend: Backend, bytes: &[u8], seed: Option<u128>) -> u128 {
+    match seed {
+        None if bytes.len() <= 900 => city_hash128(bytes),
+        Some(seed) if bytes.len() <= 900 => city_hash128_with_seed(bytes, seed),
+        None => city256_crc_with(backend, bytes, 0).hi,
+        Some(seed) => crc128_with_seed(city256_crc_with(backend, bytes, 0), seed),
+    }
+}
 
-        let u = ((seed >> 64) as u64).wrapping_add(result_lo);
-        let v = ((seed & 0xffff_ffff_ffff_ffff) as u64).wrapping_add(result_hi);
+/// Mixes `seed` into the CRC256 core of an input longer than 900 bytes.
+fn crc128_with_seed(hash: Hash256, seed: u128) -> u128 {
+    let result_lo = (hash.lo & 0xffff_ffff_ffff_ffff) as u64;
+    let result_hi = (hash.lo >> 64) as u64;
 
-        let l0 = hash_len_16_u64(u, v.wrapping_add(result1_lo));
-        let l1 = hash_len_16_u64(
-            v.rotate_right(32),
-            u.wrapping_mul(K0).wrapping_add(result1_hi),
-        );
-        (l0 as u128) | (l1 as u128).wrapping_shl(64)
+    let result1_lo = (hash.hi & 0xffff_ffff_ffff_ffff) as u64;
+    let result1_hi = (hash.hi >> 64) as u64;
+
+    let u = ((seed >> 64) as u64).wrapping_add(result_lo);
+    let v = ((seed & 0xffff_ffff_ffff_ffff) as u64).wrapping_add(result_hi);
+
+    let l0 = hash_len_16_u64(u, v.wrapping_add(result1_lo));
+    let l1 = hash_len_16_u64(
+        v.rotate_right(32),
+        u.wrapping_mul(K0).wrapping_add(result1_hi),
+    );
+    (l0 as u128) | (l1 as u128).wrapping_shl(64)
+}
+
+/// Computes [`city_hash128_crc_with_seed`] of one input for many seeds at once.
+///
+/// Inputs longer than 900 bytes mix the seed in after the CRC256 core, which
+/// then runs once for all seeds. Shorter inputs, like [`city_hash128_with_seed`],
+/// start from the seed and are hashed once per seed.
+///
+/// # Parameters
+///
+/// - `bytes`: The input byte slice to hash.
+/// - `seeds`: The 128-bit seeds to hash with.
+/// - `out`: Receives `city_hash128_crc_with_seed(bytes, seeds[i])` at index `i`.
+///
+/// # Panics
+///
+/// Panics if `seeds` and `out` differ in length.
+pub fn city_hash128_crc_with_seeds_many(bytes: &[u8], seeds: &[u128], out: &mut [u128]) {
+    assert_eq!(
+        seeds.len(),
+        out.len(),
+        "seeds and out must have equal length"
+    );
+    if bytes.len() <= 900 {
+        for (dst, &seed) in out.iter_mut().zip(seeds) {
+            *dst = city_hash128_with_seed(bytes, seed);
+        }
+    } else {
+        let hash = city_hash256_crc(bytes);
+        for (dst, &seed) in out.iter_mut().zip(seeds) {
+            *dst = crc128_with_seed(hash, seed);
+        }
     }
 }
 
@@ -294,4 +336,17 @@ mod tests {
         let hash2 = city_128_with_seed_impl(data2, 125);
         assert_eq!(hash2, 119575411414761893753960023769141284138);
     }
+
+    #[test]
+    fn test_crc_with_seeds_many() {
+        let data: Vec<u8> = (0..2000u32).map(|i| (i * 13 + 5) as u8).collect();
+        let seeds = [0, 1, u128::MAX, (K0 as u128) << 64 | 1234567];
+        for len in [0, 15, 100, 900, 901, 2000] {
+            let mut out = [0u128; 4];
+            city_hash128_crc_with_seeds_many(&data[..len], &seeds, &mut out);
+            for (hash, seed) in out.iter().zip(seeds) {
+                assert_eq!(*hash, city_hash128_crc_with_seed(&data[..len], seed));
+            }
+        }
+    }
 }
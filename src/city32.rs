@@ -27,11 +27,11 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
-const C1: u32 = 0xcc9e2d51;
-const C2: u32 = 0x1b873593;
+pub(crate) const C1: u32 = 0xcc9e2d51;
+pub(crate) const C2: u32 = 0x1b873593;
 
 #[inline]
-fn fmix(mut h: u32) -> u32 {
+pub(crate) fn fmix(mut h: u32) -> u32 {
     h ^= h >> 16;
     h = h.wrapping_mul(0x85ebca6b);
     h ^= h >> 13;
@@ -41,7 +41,7 @@ fn fmix(mut h: u32) -> u32 {
 }
 
 #[inline]
-fn mur(mut a: u32, mut h: u32) -> u32 {
+pub(crate) fn mur(mut a: u32, mut h: u32) -> u32 {
     // Helper from Murmur3 for combining two 32-bit values.
     a = a.wrapping_mul(C1);
     a = a.rotate_right(17);
@@ -69,21 +69,17 @@ fn hash32_len5to12(bytes: &[u8]) -> u32 {
     let mut b = a * 5;
     let mut c = 9;
     let d = b;
-    let u0 = &bytes[..4];
-    let start1 = len - 4;
-    let u1 = &bytes[start1..start1 + 4];
-    let start2 = (len >> 1) & 4;
-    let u2 = &bytes[start2..start2 + 4];
-    a += u32::from_ne_bytes([u0[0], u0[1], u0[2], u0[3]]).to_le();
-    b += u32::from_ne_bytes([u1[0], u1[1], u1[2], u1[3]]).to_le();
-    c += u32::from_ne_bytes([u2[0], u2[1], u2[2], u2[3]]).to_le();
+    a += read_le32(bytes, 0);
+    b += read_le32(bytes, len - 4);
+    c += read_le32(bytes, (len >> 1) & 4);
     fmix(mur(c, mur(b, mur(a, d))))
 }
 
+/// Reads the 4 bytes at `from_start` as a little-endian word, like the C++
+/// `Fetch32` does on hosts of either byte order.
 #[inline]
 pub(crate) fn read_le32(bytes: &[u8], from_start: usize) -> u32 {
-    let u = &bytes[from_start..from_start + 4];
-    u32::from_ne_bytes([u[0], u[1], u[2], u[3]]).to_le()
+    u32::from_le_bytes(bytes[from_start..from_start + 4].try_into().unwrap())
 }
 
 #[inline]
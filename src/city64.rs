@@ -28,10 +28,11 @@
  */
 use crate::city32::read_le32;
 
+/// Reads the 8 bytes at `from_start` as a little-endian word, like the C++
+/// `Fetch64` does on hosts of either byte order.
 #[inline]
 pub(crate) fn read_le64(bytes: &[u8], from_start: usize) -> u64 {
-    let u = &bytes[from_start..from_start + 8];
-    u64::from_ne_bytes([u[0], u[1], u[2], u[3], u[4], u[5], u[6], u[7]]).to_le()
+    u64::from_le_bytes(bytes[from_start..from_start + 8].try_into().unwrap())
 }
 
 // Some primes between 2^63 and 2^64 for various uses.
@@ -180,8 +181,36 @@ pub(crate) fn hash64_len_33_to_64(bytes: &[u8]) -> u64 {
     b.wrapping_add(x)
 }
 
+/// Hashes a 128-bit number down to 64 bits, as C++ `Hash128to64` does.
+///
+/// Useful to combine two 64-bit hashes, or to shorten a 128-bit one.
+///
+/// # Arguments
+///
+/// * `x` - The value to hash; `Uint128Low64(x)` is the low half.
+///
+/// # Returns
+///
+/// A `u64` hash of `x`.
 #[inline]
-fn city_hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
+pub fn hash128_to_64(x: u128) -> u64 {
+    hash_len_16_u64(x as u64, (x >> 64) as u64)
+}
+
+/// Computes a 64-bit CityHash of `bytes` mixed with two seeds, as C++
+/// `CityHash64WithSeeds` does.
+///
+/// The seeds are applied after the unseeded hash: the result is
+/// `Hash128to64(CityHash64(bytes) - seed0, seed1)`.
+///
+/// # Parameters
+/// - `bytes`: The input data to hash.
+/// - `seed0`: Subtracted from the unseeded hash.
+/// - `seed1`: Mixed with the difference.
+///
+/// # Returns
+/// A 64-bit hash value computed from `bytes` and both seeds.
+pub fn city_hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
     hash_len_16_u64(city_hash64(bytes).wrapping_sub(seed0), seed1)
 }
 
@@ -198,10 +227,39 @@ fn city_hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
 /// - `bytes`: The input data to hash.
 /// - `seed`: A 64-bit seed value used to randomize the hash output.
 ///
+/// This is C++ `CityHash64WithSeed`, i.e. [`city_hash64_with_seeds`] with `k2`
+/// as the first seed. Before 0.2.0 the first seed was `k0`, which does not
+/// match upstream; see `CHANGELOG.md`.
+///
 /// # Returns
 /// A 64-bit hash value computed from `bytes` and `seed`.
 pub fn city_hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
-    city_hash64_with_seeds(bytes, K0, seed)
+    city_hash64_with_seeds(bytes, K2, seed)
+}
+
+/// Computes [`city_hash64_with_seed`] of one input for many seeds at once.
+///
+/// The seed is only mixed in after the unseeded CityHash64 core, so the core
+/// runs once and each seed costs a single final mix. This suits Bloom filters,
+/// MinHash and cuckoo tables that need `k` independent hashes of one key.
+///
+/// # Parameters
+/// - `bytes`: The input data to hash.
+/// - `seeds`: The seeds to hash with.
+/// - `out`: Receives `city_hash64_with_seed(bytes, seeds[i])` at index `i`.
+///
+/// # Panics
+/// Panics if `seeds` and `out` differ in length.
+pub fn city_hash64_with_seeds_many(bytes: &[u8], seeds: &[u64], out: &mut [u64]) {
+    assert_eq!(
+        seeds.len(),
+        out.len(),
+        "seeds and out must have equal length"
+    );
+    let hash = city_hash64(bytes).wrapping_sub(K2);
+    for (dst, &seed) in out.iter_mut().zip(seeds) {
+        *dst = hash_len_16_u64(hash, seed);
+    }
 }
 
 /// Computes the 64-bit CityHash of a byte slice.
@@ -228,9 +286,13 @@ pub fn city_hash64(bytes: &[u8]) -> u64 {
     } else if len <= 64 {
         return hash64_len_33_to_64(bytes);
     }
+    city_hash64_long(bytes)
+}
 
-    // For strings over 64 bytes we hash the end first, and then as we
-    // loop we keep 56 bytes of state: v, w, x, y, and z.
+#[inline]
+pub(crate) fn city_hash64_long(bytes: &[u8]) -> u64 {
+    debug_assert!(bytes.len() > 64);
+    let len = bytes.len();
     // For strings over 64 bytes we hash the end first, and then as we
     // loop we keep 56 bytes of state: v, w, x, y, and z.
     let mut x = read_le64(bytes, len - 40);
@@ -276,7 +338,42 @@ pub fn city_hash64(bytes: &[u8]) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use super::city_hash64;
+    use super::*;
+
+    #[test]
+    fn test_read_le() {
+        let bytes = [0xffu8, 1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(read_le64(&bytes, 1), 0x0807_0605_0403_0201);
+        assert_eq!(read_le32(&bytes, 1), 0x0403_0201);
+        assert_eq!(read_le32(&bytes, 5), 0x0807_0605);
+    }
+
+    #[test]
+    fn test_hash128_to_64() {
+        assert_eq!(hash128_to_64(0), hash_len_16_u64(0, 0));
+        let x = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+        assert_eq!(
+            hash128_to_64(x),
+            hash_len_16_u64(0xfedc_ba98_7654_3210, 0x0123_4567_89ab_cdef)
+        );
+        assert_eq!(
+            city_hash64_with_seeds(b"abc", 5, 7),
+            hash128_to_64((city_hash64(b"abc").wrapping_sub(5) as u128) | (7u128 << 64))
+        );
+    }
+
+    #[test]
+    fn test_with_seeds_many() {
+        let data: Vec<u8> = (0..300u32).map(|i| (i * 7 + 3) as u8).collect();
+        let seeds = [0, 1, 42, K0, u64::MAX, 0x0123_4567_89ab_cdef];
+        for len in [0, 3, 16, 17, 33, 64, 65, 300] {
+            let mut out = [0u64; 6];
+            city_hash64_with_seeds_many(&data[..len], &seeds, &mut out);
+            for (hash, seed) in out.iter().zip(seeds) {
+                assert_eq!(*hash, city_hash64_with_seed(&data[..len], seed));
+            }
+        }
+    }
 
     #[test]
     fn test_empty() {
@@ -0,0 +1,252 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! The known-answer harness of CityHash v1.1 `city-test.cc`.
+//!
+//! Row `i` of `testdata/city_v1_1.txt` holds the upstream results for
+//! `data[i * i..i * i + i]`, and the last row for the whole buffer, where
+//! `data` is the 1 MiB of [`city_test_data`]. Every public function with an
+//! upstream counterpart is checked against every row, and the CRC functions
+//! once per available [`Backend`].
+//!
+//! The harness is what proves outputs are independent of the host's byte
+//! order: CI runs it under Miri on the big-endian `s390x-unknown-linux-gnu`.
+//! Miri skips the last row, which hashes the whole 1 MiB, and generates only
+//! the bytes the other rows read.
+use crate::backend::Backend;
+use crate::city64::K0;
+use crate::city128::city128_crc_with;
+use crate::city256::city256_crc_with;
+use crate::legacy::test_input;
+use crate::*;
+use std::hash::Hasher;
+
+const SEED0: u64 = 1234567;
+const SEED1: u64 = K0;
+const SEED128: u128 = SEED0 as u128 | ((SEED1 as u128) << 64);
+
+/// Rows of the table.
+const ROWS: usize = 300;
+
+/// A row of the table: the fifteen 64-bit results and CityHash32 last.
+type Row = [u64; 16];
+
+/// Test data of the v1.1 `city-test.cc`: 1 MiB of pseudo-random bytes.
+fn city_test_data() -> Vec<u8> {
+    city_test_bytes(1 << 20)
+}
+
+include!("../testdata/city_test_data.rs");
+
+fn table() -> Vec<Row> {
+    let rows: Vec<Row> = include_str!("../testdata/city_v1_1.txt")
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut row = [0u64; 16];
+            let mut words = line.split_whitespace();
+            for word in row.iter_mut() {
+                *word = u64::from_str_radix(words.next().unwrap(), 16).unwrap();
+            }
+            row
+        })
+        .collect();
+    assert_eq!(rows.len(), ROWS);
+    rows
+}
+
+/// The data and the rows to check it against.
+fn known_answers() -> (Vec<u8>, Vec<Row>) {
+    let mut table = table();
+    if cfg!(miri) {
+        table.pop();
+        let last = ROWS - 2;
+        return (city_test_bytes(last * last + last), table);
+    }
+    (city_test_data(), table)
+}
+
+fn halves(x: u128) -> [u64; 2] {
+    [x as u64, (x >> 64) as u64]
+}
+
+#[test]
+fn test_city_hash64() {
+    let (data, table) = known_answers();
+    for (i, row) in table.iter().enumerate() {
+        let input = test_input(&data, i, ROWS);
+        let len = input.len();
+        assert_eq!(city_hash64(input), row[0], "row {i}, length {len}");
+        assert_eq!(
+            city_hash64_with_seed(input, SEED0),
+            row[1],
+            "row {i}, length {len}"
+        );
+        assert_eq!(
+            city_hash64_with_seeds(input, SEED0, SEED1),
+            row[2],
+            "row {i}, length {len}"
+        );
+        assert_eq!(
+            hash128_to_64((row[0].wrapping_sub(SEED0) as u128) | ((SEED1 as u128) << 64)),
+            row[2],
+            "row {i}, length {len}"
+        );
+
+        let mut many = [0u64; 2];
+        city_hash64_with_seeds_many(input, &[SEED0, 0], &mut many);
+        assert_eq!(
+            many,
+            [row[1], city_hash64_with_seed(input, 0)],
+            "row {i}, length {len}"
+        );
+
+        let mut hasher = City64Hasher::new();
+        hasher.write(input);
+        assert_eq!(hasher.finish(), row[0], "row {i}, length {len}");
+        let mut hasher = City64Hasher::new_with_seed(SEED0);
+        hasher.write(input);
+        assert_eq!(hasher.finish(), row[1], "row {i}, length {len}");
+    }
+}
+
+#[test]
+fn test_city_hash32() {
+    let (data, table) = known_answers();
+    for (i, row) in table.iter().enumerate() {
+        let input = test_input(&data, i, ROWS);
+        assert_eq!(
+            city_hash32(input) as u64,
+            row[15],
+            "row {i}, length {}",
+            input.len()
+        );
+        let mut hasher = City32Hasher::default();
+        hasher.write(input);
+        assert_eq!(hasher.finish(), row[15], "row {i}, length {}", input.len());
+    }
+}
+
+#[test]
+fn test_city_hash128() {
+    let (data, table) = known_answers();
+    for (i, row) in table.iter().enumerate() {
+        let input = test_input(&data, i, ROWS);
+        let len = input.len();
+        assert_eq!(
+            halves(city_hash128(input)),
+            row[3..5],
+            "row {i}, length {len}"
+        );
+        let seeded = halves(city_hash128_with_seed(input, SEED128));
+        assert_eq!(seeded, row[5..7], "row {i}, length {len}");
+        // CityHash128WithSeed is CityMurmur below 128 bytes.
+        if len < 128 {
+            assert_eq!(
+                halves(city_murmur_with_seed(input, SEED128)),
+                row[5..7],
+                "row {i}, length {len}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_city_hash_crc() {
+    let (data, table) = known_answers();
+    for backend in Backend::available() {
+        for (i, row) in table.iter().enumerate() {
+            let input = test_input(&data, i, ROWS);
+            let len = input.len();
+            assert_eq!(
+                halves(city128_crc_with(backend, input, None)),
+                row[7..9],
+                "row {i}, length {len} on {backend}"
+            );
+            assert_eq!(
+                halves(city128_crc_with(backend, input, Some(SEED128))),
+                row[9..11],
+                "row {i}, length {len} on {backend}"
+            );
+            let hash = city256_crc_with(backend, input, 0);
+            assert_eq!(
+                [halves(hash.lo), halves(hash.hi)].concat(),
+                row[11..15],
+                "row {i}, length {len} on {backend}"
+            );
+        }
+    }
+    for (i, row) in table.iter().enumerate() {
+        let input = test_input(&data, i, ROWS);
+        let len = input.len();
+        assert_eq!(
+            halves(city_hash128_crc(input)),
+            row[7..9],
+            "row {i}, length {len}"
+        );
+        assert_eq!(
+            halves(city_hash128_crc_with_seed(input, SEED128)),
+            row[9..11],
+            "row {i}, length {len}"
+        );
+        let mut many = [0u128; 2];
+        city_hash128_crc_with_seeds_many(input, &[SEED128, 0], &mut many);
+        assert_eq!(halves(many[0]), row[9..11], "row {i}, length {len}");
+        assert_eq!(many[1], city_hash128_crc_with_seed(input, 0), "row {i}");
+        assert_eq!(
+            city_hash_crc256_words(input),
+            row[11..15],
+            "row {i}, length {len}"
+        );
+        let hash = city_hash256_crc(input);
+        assert_eq!(
+            [halves(hash.lo), halves(hash.hi)].concat(),
+            row[11..15],
+            "row {i}, length {len}"
+        );
+    }
+}
+
+/// Checks the fixed-length functions on the rows whose length is `N`.
+macro_rules! check_fixed {
+    ($data:expr, $table:expr, $($n:literal)*) => {$(
+        let input: &[u8; $n] = $data[$n * $n..$n * $n + $n].try_into().unwrap();
+        let row = &$table[$n];
+        assert_eq!(city_hash64_fixed(input), row[0], "length {}", $n);
+        assert_eq!(halves(city_hash128_fixed(input)), row[3..5], "length {}", $n);
+    )*};
+}
+
+#[test]
+fn test_city_hash_fixed() {
+    let (data, table) = known_answers();
+    check_fixed!(data, table,
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+        33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57 58 59 60 61 62
+        63 64 65 100 127 128 129 150 200 255 256 298);
+}
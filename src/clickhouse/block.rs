@@ -0,0 +1,570 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Checksummed compressed blocks, as found in the ClickHouse native protocol
+//! and in MergeTree `.bin` files.
+//!
+//! A stream is a sequence of blocks, each laid out as:
+//!
+//! | Size | Field                                                        |
+//! |------|--------------------------------------------------------------|
+//! | 16   | CityHash128 v1.0.2 of the rest of the block, low half first  |
+//! | 1    | Method: `0x02` for NONE, `0x82` for LZ4                      |
+//! | 4    | Compressed size, little-endian, including this 9-byte header |
+//! | 4    | Uncompressed size, little-endian                             |
+//! | ...  | Payload                                                      |
+//!
+//! LZ4 payloads are raw LZ4 blocks and need the `lz4` feature.
+use crate::v1_0_2::city_hash128;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// Size of the checksum that starts every block.
+pub const CHECKSUM_SIZE: usize = 16;
+/// Size of the method byte and the two sizes that follow the checksum.
+pub const HEADER_SIZE: usize = 9;
+/// Largest compressed or uncompressed size ClickHouse accepts, 1 GiB.
+pub const MAX_BLOCK_SIZE: u32 = 0x4000_0000;
+/// Amount of data [`BlockWriter`] collects per block, ClickHouse's default
+/// `max_compress_block_size`.
+pub const DEFAULT_BLOCK_SIZE: usize = 1 << 20;
+
+/// The compression method of a block.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Method {
+    /// Stored as is, method byte `0x02`.
+    None,
+    /// A raw LZ4 block, method byte `0x82`.
+    #[cfg(feature = "lz4")]
+    Lz4,
+}
+
+impl Method {
+    /// Returns the method byte written to the block header.
+    pub fn byte(self) -> u8 {
+        match self {
+            Method::None => 0x02,
+            #[cfg(feature = "lz4")]
+            Method::Lz4 => 0x82,
+        }
+    }
+
+    /// Returns the method for a header byte, or `None` if it is unknown or
+    /// its feature is disabled.
+    pub fn from_byte(byte: u8) -> Option<Method> {
+        match byte {
+            0x02 => Some(Method::None),
+            #[cfg(feature = "lz4")]
+            0x82 => Some(Method::Lz4),
+            _ => None,
+        }
+    }
+}
+
+/// What is wrong with a [`CorruptBlock`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Corruption {
+    /// The stream ended inside the block.
+    Truncated,
+    /// The stored checksum does not match the block contents.
+    ChecksumMismatch { expected: u128, actual: u128 },
+    /// The method byte is unknown, or its feature is disabled.
+    UnsupportedMethod(u8),
+    /// The sizes in the header are out of range or inconsistent.
+    InvalidSize { compressed: u32, uncompressed: u32 },
+    /// The payload does not decompress to the uncompressed size.
+    Decompress,
+}
+
+/// A block that failed to read or verify.
+///
+/// [`BlockReader`] returns it wrapped in an [`io::Error`] of kind
+/// [`io::ErrorKind::InvalidData`]; use [`io::Error::get_ref`] to inspect it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CorruptBlock {
+    /// Offset of the start of the block's checksum in the stream.
+    pub offset: u64,
+    /// What is wrong with the block.
+    pub corruption: Corruption,
+}
+
+impl fmt::Display for CorruptBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "corrupt block at offset {}: ", self.offset)?;
+        match self.corruption {
+            Corruption::Truncated => write!(f, "unexpected end of stream"),
+            Corruption::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "checksum mismatch: expected {expected:032x}, found {actual:032x}"
+            ),
+            Corruption::UnsupportedMethod(byte) => {
+                write!(f, "unsupported compression method {byte:#04x}")
+            }
+            Corruption::InvalidSize {
+                compressed,
+                uncompressed,
+            } => write!(
+                f,
+                "invalid sizes: compressed {compressed}, uncompressed {uncompressed}"
+            ),
+            Corruption::Decompress => write!(f, "payload does not decompress"),
+        }
+    }
+}
+
+impl std::error::Error for CorruptBlock {}
+
+impl CorruptBlock {
+    fn into_io(self) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, self)
+    }
+}
+
+/// Reads into `buf` until it is full or the reader is exhausted, returning
+/// the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// Encodes `data` as one block.
+///
+/// # Arguments
+///
+/// * `method` - The compression method of the block.
+/// * `data` - The uncompressed contents, at most [`MAX_BLOCK_SIZE`] bytes.
+///
+/// # Returns
+///
+/// The block, checksum included.
+///
+/// # Panics
+///
+/// Panics if `data` or its compressed form is larger than [`MAX_BLOCK_SIZE`].
+pub fn encode_block(method: Method, data: &[u8]) -> Vec<u8> {
+    assert!(
+        data.len() <= MAX_BLOCK_SIZE as usize,
+        "block of {} bytes exceeds the maximum of {MAX_BLOCK_SIZE}",
+        data.len()
+    );
+    let mut block = vec![0u8; CHECKSUM_SIZE + HEADER_SIZE];
+    match method {
+        Method::None => block.extend_from_slice(data),
+        #[cfg(feature = "lz4")]
+        Method::Lz4 => block.extend_from_slice(&lz4_flex::block::compress(data)),
+    }
+    let compressed = block.len() - CHECKSUM_SIZE;
+    assert!(
+        compressed <= MAX_BLOCK_SIZE as usize,
+        "compressed block of {compressed} bytes exceeds the maximum of {MAX_BLOCK_SIZE}"
+    );
+    block[CHECKSUM_SIZE] = method.byte();
+    block[CHECKSUM_SIZE + 1..CHECKSUM_SIZE + 5].copy_from_slice(&(compressed as u32).to_le_bytes());
+    block[CHECKSUM_SIZE + 5..CHECKSUM_SIZE + 9].copy_from_slice(&(data.len() as u32).to_le_bytes());
+    let checksum = city_hash128(&block[CHECKSUM_SIZE..]);
+    block[..CHECKSUM_SIZE].copy_from_slice(&checksum.to_le_bytes());
+    block
+}
+
+/// Reads and verifies the blocks of a stream.
+///
+/// Use [`BlockReader::read_block`] to get the blocks one by one, or the
+/// [`Read`] implementation to get the concatenated uncompressed contents.
+/// Any block that fails to verify is reported as a [`CorruptBlock`].
+pub struct BlockReader<R> {
+    inner: R,
+    offset: u64,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> BlockReader<R> {
+    /// Creates a reader of the blocks in `inner`.
+    pub fn new(inner: R) -> Self {
+        BlockReader {
+            inner,
+            offset: 0,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Returns the stream offset of the next block.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads, verifies and decompresses the next block.
+    ///
+    /// # Returns
+    ///
+    /// The uncompressed contents of the block, or `None` at the end of the
+    /// stream.
+    pub fn read_block(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let offset = self.offset;
+        let corrupt = |corruption| CorruptBlock { offset, corruption }.into_io();
+
+        let mut head = [0u8; CHECKSUM_SIZE + HEADER_SIZE];
+        match read_full(&mut self.inner, &mut head)? {
+            0 => return Ok(None),
+            n if n < head.len() => return Err(corrupt(Corruption::Truncated)),
+            _ => {}
+        }
+        let expected = u128::from_le_bytes(head[..CHECKSUM_SIZE].try_into().unwrap());
+        let header = &head[CHECKSUM_SIZE..];
+        let compressed = u32::from_le_bytes(header[1..5].try_into().unwrap());
+        let uncompressed = u32::from_le_bytes(header[5..9].try_into().unwrap());
+        if (compressed as usize) < HEADER_SIZE
+            || compressed > MAX_BLOCK_SIZE
+            || uncompressed > MAX_BLOCK_SIZE
+        {
+            return Err(corrupt(Corruption::InvalidSize {
+                compressed,
+                uncompressed,
+            }));
+        }
+
+        let mut block = vec![0u8; compressed as usize];
+        block[..HEADER_SIZE].copy_from_slice(header);
+        if read_full(&mut self.inner, &mut block[HEADER_SIZE..])? < block.len() - HEADER_SIZE {
+            return Err(corrupt(Corruption::Truncated));
+        }
+        let actual = city_hash128(&block);
+        if actual != expected {
+            return Err(corrupt(Corruption::ChecksumMismatch { expected, actual }));
+        }
+
+        let payload = &block[HEADER_SIZE..];
+        let data = match Method::from_byte(block[0]) {
+            Some(Method::None) => {
+                if payload.len() != uncompressed as usize {
+                    return Err(corrupt(Corruption::InvalidSize {
+                        compressed,
+                        uncompressed,
+                    }));
+                }
+                payload.to_vec()
+            }
+            #[cfg(feature = "lz4")]
+            Some(Method::Lz4) => {
+                let mut data = vec![0u8; uncompressed as usize];
+                match lz4_flex::block::decompress_into(payload, &mut data) {
+                    Ok(n) if n == data.len() => data,
+                    _ => return Err(corrupt(Corruption::Decompress)),
+                }
+            }
+            None => return Err(corrupt(Corruption::UnsupportedMethod(block[0]))),
+        };
+        self.offset += (CHECKSUM_SIZE + block.len()) as u64;
+        Ok(Some(data))
+    }
+}
+
+impl<R: Read> Read for BlockReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.buffer.len() {
+            match self.read_block()? {
+                Some(data) => {
+                    self.buffer = data;
+                    self.position = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.buffer.len() - self.position);
+        buf[..n].copy_from_slice(&self.buffer[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Writes data as a stream of blocks.
+///
+/// Written data is collected into blocks of up to the block size; call
+/// [`BlockWriter::finish`] to write the last, partial block. Dropping the
+/// writer discards it.
+pub struct BlockWriter<W: Write> {
+    inner: W,
+    method: Method,
+    block_size: usize,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> BlockWriter<W> {
+    /// Creates a writer of [`DEFAULT_BLOCK_SIZE`] blocks.
+    pub fn new(inner: W, method: Method) -> Self {
+        Self::with_block_size(inner, method, DEFAULT_BLOCK_SIZE)
+    }
+
+    /// Creates a writer of blocks of up to `block_size` uncompressed bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero or larger than [`MAX_BLOCK_SIZE`].
+    pub fn with_block_size(inner: W, method: Method, block_size: usize) -> Self {
+        assert!(
+            block_size > 0 && block_size <= MAX_BLOCK_SIZE as usize,
+            "block size must be in 1..={MAX_BLOCK_SIZE}"
+        );
+        BlockWriter {
+            inner,
+            method,
+            block_size,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Writes `data` as one block of its own, after any pending data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is larger than [`MAX_BLOCK_SIZE`].
+    pub fn write_block(&mut self, data: &[u8]) -> io::Result<()> {
+        self.flush_block()?;
+        self.inner.write_all(&encode_block(self.method, data))
+    }
+
+    /// Writes the pending partial block and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.inner
+                .write_all(&encode_block(self.method, &self.buffer))?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for BlockWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.block_size - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..n]);
+        if self.buffer.len() == self.block_size {
+            self.flush_block()?;
+        }
+        Ok(n)
+    }
+
+    /// Writes the pending data as a block, which may be shorter than the
+    /// block size, and flushes the underlying writer.
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_block()?;
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &[u8] = include_bytes!("../../testdata/clickhouse_blocks.bin");
+
+    fn corruption(err: io::Error) -> CorruptBlock {
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        *err.get_ref()
+            .unwrap()
+            .downcast_ref::<CorruptBlock>()
+            .unwrap()
+    }
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|i| (i % 251) as u8 ^ (i / 1000) as u8)
+            .collect()
+    }
+
+    fn methods() -> Vec<Method> {
+        vec![
+            Method::None,
+            #[cfg(feature = "lz4")]
+            Method::Lz4,
+        ]
+    }
+
+    #[test]
+    fn test_fixture_checksum() {
+        // The fixture was written with the C++ CityHash v1.0.2 that ClickHouse
+        // vendors.
+        let checksum = u128::from_le_bytes(FIXTURE[..16].try_into().unwrap());
+        assert_eq!(checksum, 0xf37e0d467dc4430459ca1fc7f1827e31);
+        assert_eq!(
+            &encode_block(Method::None, b"ClickHouse compressed block\n")[..],
+            &FIXTURE[..53]
+        );
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn test_fixture() {
+        let mut reader = BlockReader::new(FIXTURE);
+        assert_eq!(
+            reader.read_block().unwrap().unwrap(),
+            b"ClickHouse compressed block\n"
+        );
+        assert_eq!(reader.offset(), 53);
+        let mut expected = vec![b'a'; 21];
+        expected.extend_from_slice(b"tail!");
+        assert_eq!(reader.read_block().unwrap().unwrap(), expected);
+        assert_eq!(reader.read_block().unwrap().unwrap(), b"end of stream\n");
+        assert!(reader.read_block().unwrap().is_none());
+        assert_eq!(reader.offset(), FIXTURE.len() as u64);
+    }
+
+    #[cfg(not(feature = "lz4"))]
+    #[test]
+    fn test_fixture_without_lz4() {
+        let mut reader = BlockReader::new(FIXTURE);
+        reader.read_block().unwrap().unwrap();
+        let err = corruption(reader.read_block().unwrap_err());
+        assert_eq!(err.offset, 53);
+        assert_eq!(err.corruption, Corruption::UnsupportedMethod(0x82));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for method in methods() {
+            for len in [0, 1, 999, 4096, 10_000] {
+                let data = sample(len);
+                let mut writer = BlockWriter::with_block_size(Vec::new(), method, 4096);
+                for chunk in data.chunks(700) {
+                    writer.write_all(chunk).unwrap();
+                }
+                let stream = writer.finish().unwrap();
+
+                let mut reader = BlockReader::new(&stream[..]);
+                let mut blocks = 0;
+                let mut decoded = Vec::new();
+                while let Some(block) = reader.read_block().unwrap() {
+                    assert!(block.len() <= 4096);
+                    decoded.extend_from_slice(&block);
+                    blocks += 1;
+                }
+                assert_eq!(decoded, data, "{method:?} {len}");
+                assert_eq!(blocks, len.div_ceil(4096));
+
+                let mut read = Vec::new();
+                BlockReader::new(&stream[..])
+                    .read_to_end(&mut read)
+                    .unwrap();
+                assert_eq!(read, data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_block() {
+        for method in methods() {
+            let mut writer = BlockWriter::new(Vec::new(), method);
+            writer.write_all(b"pending").unwrap();
+            writer.write_block(b"whole").unwrap();
+            let stream = writer.finish().unwrap();
+            let mut reader = BlockReader::new(&stream[..]);
+            assert_eq!(reader.read_block().unwrap().unwrap(), b"pending");
+            assert_eq!(reader.read_block().unwrap().unwrap(), b"whole");
+            assert!(reader.read_block().unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        for method in methods() {
+            let mut stream = encode_block(method, &sample(100));
+            let second = stream.len();
+            stream.extend_from_slice(&encode_block(method, &sample(300)));
+            stream[second + 40] ^= 0x10;
+
+            let mut reader = BlockReader::new(&stream[..]);
+            assert_eq!(reader.read_block().unwrap().unwrap(), sample(100));
+            let err = corruption(reader.read_block().unwrap_err());
+            assert_eq!(err.offset, second as u64);
+            assert!(matches!(
+                err.corruption,
+                Corruption::ChecksumMismatch { expected, actual } if expected != actual
+            ));
+        }
+    }
+
+    #[test]
+    fn test_truncated() {
+        let stream = encode_block(Method::None, b"some data");
+        for len in [1, 20, stream.len() - 1] {
+            let err = corruption(BlockReader::new(&stream[..len]).read_block().unwrap_err());
+            assert_eq!(
+                err,
+                CorruptBlock {
+                    offset: 0,
+                    corruption: Corruption::Truncated
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsupported_method() {
+        // A ZSTD block with a valid checksum.
+        let mut block = encode_block(Method::None, b"zstd");
+        block[CHECKSUM_SIZE] = 0x90;
+        let checksum = city_hash128(&block[CHECKSUM_SIZE..]);
+        block[..CHECKSUM_SIZE].copy_from_slice(&checksum.to_le_bytes());
+        let err = corruption(BlockReader::new(&block[..]).read_block().unwrap_err());
+        assert_eq!(err.corruption, Corruption::UnsupportedMethod(0x90));
+        assert!(err.to_string().contains("0x90"));
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        let mut block = encode_block(Method::None, b"data");
+        block[CHECKSUM_SIZE + 1..CHECKSUM_SIZE + 5].copy_from_slice(&3u32.to_le_bytes());
+        let err = corruption(BlockReader::new(&block[..]).read_block().unwrap_err());
+        assert_eq!(
+            err.corruption,
+            Corruption::InvalidSize {
+                compressed: 3,
+                uncompressed: 4
+            }
+        );
+    }
+}
@@ -0,0 +1,349 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! ClickHouse's `cityHash64` function and, in [`block`], the framing of its
+//! checksummed compressed blocks.
+//!
+//! ClickHouse hashes each argument according to its type and folds the
+//! results left to right with `Hash128to64`:
+//!
+//! * integers up to 64 bits are zero-extended to `UInt64` and mixed with
+//!   `intHash64`, so `Int8` `-1` hashes like `UInt64` `255`;
+//! * `String` and `FixedString` values go through CityHash64 v1.0.2;
+//! * an `Array` contributes the mixed element count followed by the hash of
+//!   every element;
+//! * a `Tuple` is flattened, so `cityHash64((a, b))` equals `cityHash64(a, b)`.
+//!
+//! `Date`, `DateTime`, `Bool` and `Enum` values hash as their underlying
+//! `UInt16`, `UInt32`, `UInt8` and `Int8`/`Int16` values.
+pub mod block;
+
+use crate::city64::hash128_to_64;
+use crate::v1_0_2;
+
+/// What ClickHouse returns for `cityHash64()` with no arguments.
+pub const EMPTY_ARGUMENTS_HASH: u64 = 0xe28dbde7fe22e41c;
+
+/// A typed argument of [`city_hash64`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Value<'a> {
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    /// A `String`, hashed without any terminator.
+    String(&'a [u8]),
+    /// A `FixedString(N)`, including its zero padding.
+    FixedString(&'a [u8]),
+    /// An `Array` of values of one type.
+    Array(&'a [Value<'a>]),
+    /// A `Tuple`, flattened into the surrounding arguments.
+    Tuple(&'a [Value<'a>]),
+}
+
+macro_rules! value_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(impl From<$ty> for Value<'_> {
+            fn from(value: $ty) -> Self {
+                Value::$variant(value)
+            }
+        })*
+    };
+}
+
+value_from!(
+    u8 => UInt8,
+    u16 => UInt16,
+    u32 => UInt32,
+    u64 => UInt64,
+    i8 => Int8,
+    i16 => Int16,
+    i32 => Int32,
+    i64 => Int64,
+);
+
+impl<'a> From<&'a str> for Value<'a> {
+    fn from(value: &'a str) -> Self {
+        Value::String(value.as_bytes())
+    }
+}
+
+impl<'a> From<&'a [u8]> for Value<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Value::String(value)
+    }
+}
+
+/// ClickHouse `intHash64`: the MurmurHash3 finalizer.
+#[inline]
+fn int_hash64(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51afd7ed558ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ceb9fe1a85ec53);
+    x ^= x >> 33;
+    x
+}
+
+/// ClickHouse `IntHash64Impl::apply`.
+#[inline]
+fn int_hash(x: u64) -> u64 {
+    int_hash64(x ^ 0x4cf2d2baae6da887)
+}
+
+#[inline]
+fn combine(h1: u64, h2: u64) -> u64 {
+    hash128_to_64(h1 as u128 | ((h2 as u128) << 64))
+}
+
+#[inline]
+fn push(acc: &mut Option<u64>, hash: u64) {
+    *acc = Some(match *acc {
+        Some(prev) => combine(prev, hash),
+        None => hash,
+    });
+}
+
+fn feed(value: &Value<'_>, acc: &mut Option<u64>) {
+    match *value {
+        Value::UInt8(x) => push(acc, int_hash(x as u64)),
+        Value::UInt16(x) => push(acc, int_hash(x as u64)),
+        Value::UInt32(x) => push(acc, int_hash(x as u64)),
+        Value::UInt64(x) => push(acc, int_hash(x)),
+        Value::Int8(x) => push(acc, int_hash(x as u8 as u64)),
+        Value::Int16(x) => push(acc, int_hash(x as u16 as u64)),
+        Value::Int32(x) => push(acc, int_hash(x as u32 as u64)),
+        Value::Int64(x) => push(acc, int_hash(x as u64)),
+        Value::String(s) | Value::FixedString(s) => push(acc, v1_0_2::city_hash64(s)),
+        Value::Array(items) => {
+            push(acc, int_hash(items.len() as u64));
+            for item in items {
+                let mut element = None;
+                feed(item, &mut element);
+                push(acc, element.unwrap_or(EMPTY_ARGUMENTS_HASH));
+            }
+        }
+        Value::Tuple(items) => {
+            for item in items {
+                feed(item, acc);
+            }
+        }
+    }
+}
+
+/// Computes ClickHouse `cityHash64(args...)`.
+///
+/// # Arguments
+///
+/// * `args` - The arguments, in the order they appear in the SQL call.
+///
+/// # Returns
+///
+/// The `UInt64` ClickHouse returns, e.g. for use as a sharding key.
+pub fn city_hash64(args: &[Value<'_>]) -> u64 {
+    let mut acc = None;
+    for arg in args {
+        feed(arg, &mut acc);
+    }
+    acc.unwrap_or(EMPTY_ARGUMENTS_HASH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documented_example() {
+        // SELECT cityHash64(array('e','x','a'), 'mple', 10,
+        //     toDateTime('2019-06-15 23:00:00')) from the ClickHouse
+        // documentation, run with the Europe/Moscow server time zone.
+        let letters = [Value::from("e"), Value::from("x"), Value::from("a")];
+        let hash = city_hash64(&[
+            Value::Array(&letters),
+            Value::from("mple"),
+            Value::UInt8(10),
+            Value::UInt32(1560628800),
+        ]);
+        assert_eq!(hash, 12072650598913549138);
+    }
+
+    /// [`city_hash64`] of a case of `testdata/clickhouse_city_hash64.sh`.
+    fn case(id: &str) -> u64 {
+        let x300 = [b'x'; 300];
+        let pairs = [
+            [Value::UInt8(1), Value::from("a")],
+            [Value::UInt8(2), Value::from("b")],
+        ];
+        let inner = [
+            &[Value::UInt32(1), Value::UInt32(2)][..],
+            &[],
+            &[Value::UInt32(3)],
+        ];
+        match id {
+            "empty_string" => city_hash64(&[Value::from("")]),
+            "string" => city_hash64(&[Value::from("abc")]),
+            "long_string" => city_hash64(&[Value::String(&x300)]),
+            "uint8" => city_hash64(&[Value::UInt8(10)]),
+            "uint64_max" => city_hash64(&[Value::UInt64(u64::MAX)]),
+            "int8_negative" => city_hash64(&[Value::Int8(-1)]),
+            "int16_negative" => city_hash64(&[Value::Int16(-300)]),
+            "int32_negative" => city_hash64(&[Value::Int32(-1)]),
+            "int64_min" => city_hash64(&[Value::Int64(i64::MIN)]),
+            "fixed_string" => city_hash64(&[Value::FixedString(b"ab\0\0\0")]),
+            "tuple" => city_hash64(&[Value::Tuple(&[Value::UInt64(42), Value::from("eu-west")])]),
+            "multi_argument" => city_hash64(&[Value::UInt64(42), Value::from("eu-west")]),
+            "empty_array" => city_hash64(&[Value::Array(&[])]),
+            "array" => city_hash64(&[Value::Array(&[Value::UInt32(1), Value::UInt32(2)])]),
+            "array_of_tuples" => city_hash64(&[Value::Array(&[
+                Value::Tuple(&pairs[0]),
+                Value::Tuple(&pairs[1]),
+            ])]),
+            "nested_array" => city_hash64(&[Value::Array(&[
+                Value::Array(inner[0]),
+                Value::Array(inner[1]),
+                Value::Array(inner[2]),
+            ])]),
+            "documented_example" => city_hash64(&[
+                Value::Array(&[Value::from("e"), Value::from("x"), Value::from("a")]),
+                Value::from("mple"),
+                Value::UInt8(10),
+                Value::UInt32(1560628800),
+            ]),
+            _ => panic!("unknown case {id}"),
+        }
+    }
+
+    #[test]
+    fn test_clickhouse_cases_known() {
+        let script = include_str!("../../testdata/clickhouse_city_hash64.sh");
+        let cases = script.split("<<'CASES'\n").nth(1).unwrap();
+        for line in cases.lines().take_while(|line| *line != "CASES") {
+            case(line.split('\t').next().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_clickhouse_vectors() {
+        let rows = include_str!("../../testdata/clickhouse_city_hash64.tsv")
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        for row in rows {
+            let columns: Vec<&str> = row.split('\t').collect();
+            let [id, args, expected] = columns[..] else {
+                panic!("malformed row {row:?}");
+            };
+            assert_eq!(
+                case(id),
+                expected.parse::<u64>().unwrap(),
+                "cityHash64({args})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_strings() {
+        // SELECT cityHash64('')
+        assert_eq!(city_hash64(&[Value::from("")]), 11160318154034397263);
+        for s in [&b"abc"[..], &[0u8; 16], &[7u8; 100]] {
+            assert_eq!(city_hash64(&[Value::String(s)]), v1_0_2::city_hash64(s));
+            assert_eq!(
+                city_hash64(&[Value::FixedString(s)]),
+                city_hash64(&[Value::String(s)])
+            );
+        }
+    }
+
+    #[test]
+    fn test_integers_zero_extend() {
+        assert_eq!(
+            city_hash64(&[Value::Int8(-1)]),
+            city_hash64(&[Value::UInt64(255)])
+        );
+        assert_eq!(
+            city_hash64(&[Value::Int32(-1)]),
+            city_hash64(&[Value::UInt32(u32::MAX)])
+        );
+        assert_eq!(
+            city_hash64(&[Value::UInt16(10)]),
+            city_hash64(&[Value::UInt8(10)])
+        );
+        assert_ne!(
+            city_hash64(&[Value::Int64(-1)]),
+            city_hash64(&[Value::Int32(-1)])
+        );
+        assert_eq!(
+            city_hash64(&[Value::UInt64(0)]),
+            int_hash64(0x4cf2d2baae6da887)
+        );
+    }
+
+    #[test]
+    fn test_tuples_flatten() {
+        let user_id = Value::UInt64(42);
+        let region = Value::from("eu-west");
+        let pair = [user_id, region];
+        assert_eq!(
+            city_hash64(&[Value::Tuple(&pair)]),
+            city_hash64(&[user_id, region])
+        );
+        assert_eq!(
+            city_hash64(&[user_id, region]),
+            combine(int_hash(42), v1_0_2::city_hash64(b"eu-west"))
+        );
+        assert_ne!(
+            city_hash64(&[user_id, region]),
+            city_hash64(&[region, user_id])
+        );
+    }
+
+    #[test]
+    fn test_arrays() {
+        assert_eq!(city_hash64(&[Value::Array(&[])]), int_hash(0));
+        let ints = [Value::UInt32(1), Value::UInt32(2)];
+        assert_eq!(
+            city_hash64(&[Value::Array(&ints)]),
+            combine(combine(int_hash(2), int_hash(1)), int_hash(2))
+        );
+        // Tuple elements are folded into one hash per array element.
+        let pair = [Value::UInt8(1), Value::from("a")];
+        let tuples = [Value::Tuple(&pair)];
+        assert_eq!(
+            city_hash64(&[Value::Array(&tuples)]),
+            combine(int_hash(1), city_hash64(&pair))
+        );
+    }
+
+    #[test]
+    fn test_no_arguments() {
+        assert_eq!(city_hash64(&[]), EMPTY_ARGUMENTS_HASH);
+    }
+}
@@ -27,6 +27,8 @@
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
 
+use crate::city64::read_le64;
+
 //   #[test]
 //     fn make_table() {
 //         let mut t = [0u32; 256];
@@ -46,7 +48,7 @@
 //         // println!("{:?}", t)
 //     }
 
-static CRC_TABLE: [u32; 256] = [
+const CRC_TABLE: [u32; 256] = [
     0x00000000, 0xF26B8303, 0xE13B70F7, 0x1350F3F4, 0xC79A971F, 0x35F1141C, 0x26A1E7E8, 0xD4CA64EB,
     0x8AD958CF, 0x78B2DBCC, 0x6BE22838, 0x9989AB3B, 0x4D43CFD0, 0xBF284CD3, 0xAC78BF27, 0x5E133C24,
     0x105EC76F, 0xE235446C, 0xF165B798, 0x030E349B, 0xD7C45070, 0x25AFD373, 0x36FF2087, 0xC494A384,
@@ -81,24 +83,94 @@ static CRC_TABLE: [u32; 256] = [
     0x79B737BA, 0x8BDCB4B9, 0x988C474D, 0x6AE7C44E, 0xBE2DA0A5, 0x4C4623A6, 0x5F16D052, 0xAD7D5351,
 ];
 
+/// Builds the slicing-by-8 tables: `tables[k][i]` is the CRC of byte `i`
+/// followed by `k` zero bytes, so eight table lookups advance the CRC by a whole word.
+const fn make_slicing_tables() -> [[u32; 256]; 8] {
+    let mut tables = [[0u32; 256]; 8];
+    tables[0] = CRC_TABLE;
+    let mut k = 1;
+    while k < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[k - 1][i];
+            tables[k][i] = (prev >> 8) ^ CRC_TABLE[(prev & 0xFF) as usize];
+            i += 1;
+        }
+        k += 1;
+    }
+    tables
+}
+
+static CRC_SLICING_TABLES: [[u32; 256]; 8] = make_slicing_tables();
+
 #[inline(always)]
-pub(crate) fn crc32c_u64(mut crc: u32, mut data: u64) -> u32 {
-    // const POLY: u32 = 0x82F63B78; // reflected form of 0x1EDC6F41
-    //
-    // for _ in 0..8 {
-    //     let byte = (data & 0xFF) as u8;
-    //     crc ^= byte as u32;
-    //     for _ in 0..8 {
-    //         crc = (crc >> 1) ^ (POLY & (!(crc & 1).wrapping_sub(1)));
-    //     }
-    //     data >>= 8;
-    // }
-    //
-    // crc
-    for _ in 0..8 {
-        let byte = (data & 0xFF) as usize;
-        crc = (crc >> 8) ^ CRC_TABLE[(crc as u8 ^ byte as u8) as usize];
-        data >>= 8;
+pub(crate) fn crc32c_u64(crc: u32, data: u64) -> u32 {
+    // Slicing-by-8: the lookups are independent of each other, unlike the
+    // byte-at-a-time loop where each step waits for the previous one.
+    let v = data ^ crc as u64;
+    let t = &CRC_SLICING_TABLES;
+    t[7][(v & 0xFF) as usize]
+        ^ t[6][((v >> 8) & 0xFF) as usize]
+        ^ t[5][((v >> 16) & 0xFF) as usize]
+        ^ t[4][((v >> 24) & 0xFF) as usize]
+        ^ t[3][((v >> 32) & 0xFF) as usize]
+        ^ t[2][((v >> 40) & 0xFF) as usize]
+        ^ t[1][((v >> 48) & 0xFF) as usize]
+        ^ t[0][(v >> 56) as usize]
+}
+
+#[inline(always)]
+pub(crate) fn crc32c_u8(crc: u32, data: u8) -> u32 {
+    (crc >> 8) ^ CRC_TABLE[(crc as u8 ^ data) as usize]
+}
+
+/// Advances the raw (unconditioned) CRC32C register over `bytes` in software.
+pub(crate) fn crc32c_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        crc = crc32c_u64(crc, read_le64(chunk, 0));
+    }
+    for &byte in chunks.remainder() {
+        crc = crc32c_u8(crc, byte);
     }
     crc
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crc32c_u64_bytewise(mut crc: u32, mut data: u64) -> u32 {
+        for _ in 0..8 {
+            crc = crc32c_u8(crc, data as u8);
+            data >>= 8;
+        }
+        crc
+    }
+
+    #[test]
+    fn test_slicing_matches_bytewise() {
+        let mut crc = 0u32;
+        let mut data = 0x0123_4567_89ab_cdefu64;
+        for _ in 0..10_000 {
+            assert_eq!(crc32c_u64(crc, data), crc32c_u64_bytewise(crc, data));
+            crc = crc.rotate_left(7) ^ crc32c_u64(crc, data);
+            data = data
+                .wrapping_mul(0x9e37_79b9_7f4a_7c15)
+                .wrapping_add(crc as u64);
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", not(feature = "portable")))]
+    fn test_slicing_matches_sse42() {
+        if std::arch::is_x86_feature_detected!("sse4.2") {
+            let mut data = 0xfeed_face_cafe_beefu64;
+            for crc in 0..1000u32 {
+                let hw = unsafe { std::arch::x86_64::_mm_crc32_u64(crc as u64, data) } as u32;
+                assert_eq!(crc32c_u64(crc, data), hw);
+                data = data.rotate_left(13).wrapping_add(crc as u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,532 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! CRC32C (Castagnoli) checksums.
+//!
+//! This is the checksum used by iSCSI, ext4, Btrfs, SCTP and many storage
+//! formats, and the same polynomial the `Crc` City variants are built on.
+//! On x86_64 with SSE4.2 and on aarch64 with the CRC extension the hardware
+//! instructions are used; when carry-less multiplication (PCLMULQDQ/PMULL)
+//! is available as well, large buffers are split into three independent
+//! streams that are folded back together.
+
+use crate::backend::{Backend, backend};
+#[cfg(all(target_arch = "x86", not(feature = "portable")))]
+use crate::city32::read_le32;
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    not(feature = "portable")
+))]
+use crate::city64::read_le64;
+use crate::crc::crc32c_update;
+use std::hash::Hasher;
+
+// Reflected form of 0x1EDC6F41.
+const POLY: u32 = 0x82F63B78;
+
+/// Multiplies two polynomials modulo the CRC polynomial, bit-reflected representation.
+/// `a` must be non-zero.
+const fn multmodp(a: u32, mut b: u32) -> u32 {
+    let mut m = 1u32 << 31;
+    let mut p = 0u32;
+    loop {
+        if a & m != 0 {
+            p ^= b;
+            if a & (m - 1) == 0 {
+                break;
+            }
+        }
+        m >>= 1;
+        b = if b & 1 != 0 { (b >> 1) ^ POLY } else { b >> 1 };
+    }
+    p
+}
+
+/// Returns `x^(8 * n) mod P`, the operator that appends `n` zero bytes to a CRC register.
+const fn x8n_mod_p(mut n: u64) -> u32 {
+    let mut p = 1u32 << 31; // x^0
+    let mut sq = 1u32 << 23; // x^8
+    while n != 0 {
+        if n & 1 != 0 {
+            p = multmodp(sq, p);
+        }
+        sq = multmodp(sq, sq);
+        n >>= 1;
+    }
+    p
+}
+
+/// Constant for shifting a CRC register by `n` bytes with one carry-less multiply
+/// followed by a 64-bit CRC instruction. The multiply contributes an extra `x^1`
+/// and the CRC instruction another `x^32`, so the constant is `x^(8n - 33)`,
+/// computed here as `x^(8(n - 5)) * x^7`.
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    not(feature = "portable")
+))]
+const fn fold_constant(n: u64) -> u64 {
+    multmodp(1u32 << 24, x8n_mod_p(n - 5)) as u64
+}
+
+// Bytes per lane for the three-way interleaved hardware loops.
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    not(feature = "portable")
+))]
+const LONG_BLOCK: usize = 8192;
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    not(feature = "portable")
+))]
+const SHORT_BLOCK: usize = 256;
+
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    not(feature = "portable")
+))]
+const K_LONG: (u64, u64) = (
+    fold_constant(LONG_BLOCK as u64),
+    fold_constant(2 * LONG_BLOCK as u64),
+);
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    not(feature = "portable")
+))]
+const K_SHORT: (u64, u64) = (
+    fold_constant(SHORT_BLOCK as u64),
+    fold_constant(2 * SHORT_BLOCK as u64),
+);
+
+#[cfg(all(target_arch = "x86_64", not(feature = "portable")))]
+#[target_feature(enable = "sse4.2")]
+fn update_sse42(crc: u32, bytes: &[u8]) -> u32 {
+    use std::arch::x86_64::{_mm_crc32_u8, _mm_crc32_u64};
+    let mut chunks = bytes.chunks_exact(8);
+    let mut crc = crc as u64;
+    for chunk in &mut chunks {
+        crc = _mm_crc32_u64(crc, read_le64(chunk, 0));
+    }
+    let mut crc = crc as u32;
+    for &byte in chunks.remainder() {
+        crc = _mm_crc32_u8(crc, byte);
+    }
+    crc
+}
+
+#[cfg(all(target_arch = "x86_64", not(feature = "portable")))]
+#[target_feature(enable = "sse4.2,pclmulqdq")]
+fn update_sse42_pclmul(mut crc: u32, mut bytes: &[u8]) -> u32 {
+    use std::arch::x86_64::{
+        _mm_clmulepi64_si128, _mm_crc32_u64, _mm_cvtsi32_si128, _mm_cvtsi64_si128,
+        _mm_cvtsi128_si64,
+    };
+
+    #[inline]
+    #[target_feature(enable = "sse4.2,pclmulqdq")]
+    fn shift(crc: u64, k: u64) -> u64 {
+        let product = _mm_clmulepi64_si128(
+            _mm_cvtsi32_si128(crc as u32 as i32),
+            _mm_cvtsi64_si128(k as i64),
+            0x00,
+        );
+        _mm_crc32_u64(0, _mm_cvtsi128_si64(product) as u64)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse4.2,pclmulqdq")]
+    fn fold3(crc: u32, bytes: &[u8], block: usize, k: (u64, u64)) -> u32 {
+        let (a, rest) = bytes.split_at(block);
+        let (b, c) = rest.split_at(block);
+        let mut crc0 = crc as u64;
+        let mut crc1 = 0u64;
+        let mut crc2 = 0u64;
+        for ((a, b), c) in a
+            .chunks_exact(8)
+            .zip(b.chunks_exact(8))
+            .zip(c.chunks_exact(8))
+        {
+            crc0 = _mm_crc32_u64(crc0, read_le64(a, 0));
+            crc1 = _mm_crc32_u64(crc1, read_le64(b, 0));
+            crc2 = _mm_crc32_u64(crc2, read_le64(c, 0));
+        }
+        (shift(crc0, k.1) ^ shift(crc1, k.0) ^ crc2) as u32
+    }
+
+    while bytes.len() >= 3 * LONG_BLOCK {
+        crc = fold3(crc, &bytes[..3 * LONG_BLOCK], LONG_BLOCK, K_LONG);
+        bytes = &bytes[3 * LONG_BLOCK..];
+    }
+    while bytes.len() >= 3 * SHORT_BLOCK {
+        crc = fold3(crc, &bytes[..3 * SHORT_BLOCK], SHORT_BLOCK, K_SHORT);
+        bytes = &bytes[3 * SHORT_BLOCK..];
+    }
+    update_sse42(crc, bytes)
+}
+
+#[cfg(all(target_arch = "x86", not(feature = "portable")))]
+#[target_feature(enable = "sse4.2")]
+fn update_sse42(mut crc: u32, bytes: &[u8]) -> u32 {
+    use std::arch::x86::{_mm_crc32_u8, _mm_crc32_u32};
+    let mut chunks = bytes.chunks_exact(4);
+    for chunk in &mut chunks {
+        crc = _mm_crc32_u32(crc, read_le32(chunk, 0));
+    }
+    for &byte in chunks.remainder() {
+        crc = _mm_crc32_u8(crc, byte);
+    }
+    crc
+}
+
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
+#[target_feature(enable = "crc")]
+fn update_arm_crc(mut crc: u32, bytes: &[u8]) -> u32 {
+    use std::arch::aarch64::{__crc32cb, __crc32cd};
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        crc = __crc32cd(crc, read_le64(chunk, 0));
+    }
+    for &byte in chunks.remainder() {
+        crc = __crc32cb(crc, byte);
+    }
+    crc
+}
+
+#[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
+#[target_feature(enable = "crc,aes")]
+fn update_arm_pmull(mut crc: u32, mut bytes: &[u8]) -> u32 {
+    use std::arch::aarch64::{__crc32cd, vmull_p64};
+
+    #[inline]
+    #[target_feature(enable = "crc,aes")]
+    fn shift(crc: u32, k: u64) -> u32 {
+        let product = vmull_p64(crc as u64, k);
+        __crc32cd(0, product as u64)
+    }
+
+    #[inline]
+    #[target_feature(enable = "crc,aes")]
+    fn fold3(crc: u32, bytes: &[u8], block: usize, k: (u64, u64)) -> u32 {
+        let (a, rest) = bytes.split_at(block);
+        let (b, c) = rest.split_at(block);
+        let mut crc0 = crc;
+        let mut crc1 = 0u32;
+        let mut crc2 = 0u32;
+        for ((a, b), c) in a
+            .chunks_exact(8)
+            .zip(b.chunks_exact(8))
+            .zip(c.chunks_exact(8))
+        {
+            crc0 = __crc32cd(crc0, read_le64(a, 0));
+            crc1 = __crc32cd(crc1, read_le64(b, 0));
+            crc2 = __crc32cd(crc2, read_le64(c, 0));
+        }
+        shift(crc0, k.1) ^ shift(crc1, k.0) ^ crc2
+    }
+
+    while bytes.len() >= 3 * LONG_BLOCK {
+        crc = fold3(crc, &bytes[..3 * LONG_BLOCK], LONG_BLOCK, K_LONG);
+        bytes = &bytes[3 * LONG_BLOCK..];
+    }
+    while bytes.len() >= 3 * SHORT_BLOCK {
+        crc = fold3(crc, &bytes[..3 * SHORT_BLOCK], SHORT_BLOCK, K_SHORT);
+        bytes = &bytes[3 * SHORT_BLOCK..];
+    }
+    update_arm_crc(crc, bytes)
+}
+
+/// Advances the raw CRC32C register on the given backend, which must be available.
+#[inline]
+pub(crate) fn crc32c_update_with(backend: Backend, crc: u32, bytes: &[u8]) -> u32 {
+    match backend {
+        #[cfg(all(target_arch = "x86_64", not(feature = "portable")))]
+        Backend::Sse42 => {
+            if std::arch::is_x86_feature_detected!("pclmulqdq") {
+                unsafe { update_sse42_pclmul(crc, bytes) }
+            } else {
+                unsafe { update_sse42(crc, bytes) }
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
+        Backend::ArmCrc => {
+            if std::arch::is_aarch64_feature_detected!("pmull") {
+                unsafe { update_arm_pmull(crc, bytes) }
+            } else {
+                unsafe { update_arm_crc(crc, bytes) }
+            }
+        }
+        #[cfg(all(target_arch = "x86", not(feature = "portable")))]
+        Backend::Sse42 => unsafe { update_sse42(crc, bytes) },
+        _ => crc32c_update(crc, bytes),
+    }
+}
+
+/// Computes the CRC32C (Castagnoli) checksum of a byte slice.
+///
+/// The result is the standard, fully conditioned CRC-32C as used by iSCSI,
+/// ext4 and Btrfs: `crc32c(b"123456789") == 0xE3069283`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to checksum.
+///
+/// # Returns
+///
+/// The 32-bit checksum of the input slice.
+pub fn crc32c(bytes: &[u8]) -> u32 {
+    crc32c_append(0, bytes)
+}
+
+/// Extends a CRC32C checksum with more data.
+///
+/// `crc32c_append(crc32c(a), b)` equals the checksum of `a` followed by `b`.
+/// Passing `0` as `crc` starts a new checksum.
+///
+/// # Arguments
+///
+/// * `crc` - The checksum of the data seen so far.
+/// * `bytes` - The data that follows it.
+///
+/// # Returns
+///
+/// The checksum of the concatenated data.
+pub fn crc32c_append(crc: u32, bytes: &[u8]) -> u32 {
+    !crc32c_update_with(backend(), !crc, bytes)
+}
+
+/// Combines the checksums of two adjacent blocks into the checksum of their concatenation.
+///
+/// Given `crc_a = crc32c(a)` and `crc_b = crc32c(b)`, returns `crc32c(a ++ b)`
+/// without touching the data, in `O(log len_b)` time. This allows blocks to be
+/// checksummed independently, e.g. in parallel, and joined afterwards.
+///
+/// # Arguments
+///
+/// * `crc_a` - Checksum of the first block.
+/// * `crc_b` - Checksum of the second block.
+/// * `len_b` - Length of the second block in bytes.
+///
+/// # Returns
+///
+/// The checksum of the first block followed by the second.
+pub fn crc32c_combine(crc_a: u32, crc_b: u32, len_b: u64) -> u32 {
+    multmodp(x8n_mod_p(len_b), crc_a) ^ crc_b
+}
+
+/// A streaming CRC32C (Castagnoli) checksum.
+///
+/// Data can be fed through [`Crc32c::update`], the [`Hasher`] interface or
+/// [`std::io::Write`], so it can be used directly as the sink of [`std::io::copy`].
+/// The result is the same as [`crc32c`] over all data written.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Crc32c {
+    crc: u32,
+}
+
+impl Crc32c {
+    /// Creates a new [`Crc32c`] with no data written.
+    pub fn new() -> Crc32c {
+        Crc32c::default()
+    }
+
+    /// Creates a new [`Crc32c`] that continues from a previously computed checksum.
+    ///
+    /// Writing `b` into `Crc32c::new_with_initial(crc32c(a))` yields the checksum of
+    /// `a` followed by `b`.
+    pub fn new_with_initial(crc: u32) -> Crc32c {
+        Crc32c { crc }
+    }
+
+    /// Feeds more data into the checksum.
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.crc = crc32c_append(self.crc, bytes);
+    }
+
+    /// Returns the checksum of all data written so far.
+    pub fn finalize(&self) -> u32 {
+        self.crc
+    }
+
+    /// Resets the checksum to the empty state.
+    pub fn reset(&mut self) {
+        self.crc = 0;
+    }
+}
+
+impl Hasher for Crc32c {
+    fn finish(&self) -> u64 {
+        self.crc as u64
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+impl std::io::Write for Crc32c {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 32) as u8
+            })
+            .collect()
+    }
+
+    fn crc32c_bitwise(bytes: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &byte in bytes {
+            crc ^= byte as u32;
+            for _ in 0..8 {
+                crc = (crc >> 1) ^ (POLY & (crc & 1).wrapping_neg());
+            }
+        }
+        !crc
+    }
+
+    #[test]
+    fn test_check_value() {
+        assert_eq!(crc32c(b"123456789"), 0xE3069283);
+        assert_eq!(crc32c(b""), 0);
+    }
+
+    #[test]
+    fn test_iscsi_vectors() {
+        // RFC 3720, appendix B.4.
+        assert_eq!(crc32c(&[0u8; 32]), 0x8A9136AA);
+        assert_eq!(crc32c(&[0xFFu8; 32]), 0x62A8AB43);
+        let ascending: Vec<u8> = (0..32).collect();
+        assert_eq!(crc32c(&ascending), 0x46DD794E);
+        let descending: Vec<u8> = (0..32).rev().collect();
+        assert_eq!(crc32c(&descending), 0x113FDB5C);
+    }
+
+    #[test]
+    fn test_matches_bitwise() {
+        let bytes = data(3 * 3 * 8192 + 3 * 256 + 77);
+        for len in (0..64).chain([767, 768, 769, 1000, 24575, 24576, 24577, bytes.len()]) {
+            assert_eq!(
+                crc32c(&bytes[..len]),
+                crc32c_bitwise(&bytes[..len]),
+                "len {len}"
+            );
+            assert_eq!(
+                !crc32c_update(!0, &bytes[..len]),
+                crc32c_bitwise(&bytes[..len]),
+                "software len {len}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_backends() {
+        let bytes = data(2 * 3 * 8192 + 5 * 256 + 13);
+        for len in [0, 7, 8, 100, 767, 768, 4000, 24576, bytes.len()] {
+            let expected = !crc32c_update(!0, &bytes[..len]);
+            for backend in Backend::available() {
+                assert_eq!(
+                    !crc32c_update_with(backend, !0, &bytes[..len]),
+                    expected,
+                    "{backend} len {len}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", not(feature = "portable")))]
+    fn test_x86_without_folding() {
+        if std::arch::is_x86_feature_detected!("sse4.2") {
+            let bytes = data(3 * 3 * LONG_BLOCK + 11);
+            assert_eq!(
+                !unsafe { update_sse42(!0, &bytes) },
+                !crc32c_update(!0, &bytes)
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "aarch64", not(feature = "portable")))]
+    fn test_arm_without_folding() {
+        if std::arch::is_aarch64_feature_detected!("crc") {
+            let bytes = data(3 * 3 * LONG_BLOCK + 11);
+            assert_eq!(
+                !unsafe { update_arm_crc(!0, &bytes) },
+                !crc32c_update(!0, &bytes)
+            );
+        }
+    }
+
+    #[test]
+    fn test_combine() {
+        let bytes = data(5000);
+        for split in [0, 1, 9, 64, 255, 2048, 4999, 5000] {
+            let (a, b) = bytes.split_at(split);
+            assert_eq!(
+                crc32c_combine(crc32c(a), crc32c(b), b.len() as u64),
+                crc32c(&bytes),
+                "split {split}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let bytes = data(1000);
+        let mut crc = Crc32c::new();
+        for chunk in bytes.chunks(37) {
+            crc.update(chunk);
+        }
+        assert_eq!(crc.finalize(), crc32c(&bytes));
+        assert_eq!(crc.finish(), crc32c(&bytes) as u64);
+
+        let mut resumed = Crc32c::new_with_initial(crc32c(&bytes[..400]));
+        std::io::copy(&mut &bytes[400..], &mut resumed).unwrap();
+        assert_eq!(resumed.finalize(), crc32c(&bytes));
+
+        resumed.reset();
+        assert_eq!(resumed, Crc32c::new());
+    }
+}
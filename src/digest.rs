@@ -0,0 +1,266 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! RustCrypto [`digest`] traits for the CityHash functions.
+//!
+//! [`City64`], [`City128`], [`City256Crc`] and [`CityMurmur`] implement
+//! [`Update`], [`FixedOutput`], [`FixedOutputReset`], [`Reset`] and
+//! [`OutputSizeUser`], so code written against those traits can take them
+//! in place of a cryptographic hash.
+//!
+//! **None of these are cryptographic hashes.** Collisions of CityHash can be
+//! found with little effort, for any seed, so they must not be used where an
+//! adversary chooses the input: signatures, MACs, password hashing or content
+//! addressing of untrusted data. For that reason they do not implement
+//! [`HashMarker`](digest::HashMarker) and so are not a [`Digest`](digest::Digest):
+//! finalize them with [`FixedOutput::finalize_fixed`] instead. They meet the
+//! bounds of `digest::dev::fixed_test`, so test vectors can be checked with
+//! `digest::new_test!(name, "vectors", City64, fixed_test)`, as
+//! `tests/digest.rs` does.
+//!
+//! CityHash is not a streaming hash, so the types buffer the input and hash
+//! it when finalized, like [`City64Hasher`](crate::City64Hasher). The output
+//! holds the 64-bit words of the result in the order of the C++ API, each
+//! little-endian: the low word of a 128-bit result comes first.
+use crate::{
+    city_hash64, city_hash64_with_seed, city_hash128, city_hash128_with_seed,
+    city_hash256_crc_with_seed, city_murmur, city_murmur_with_seed,
+};
+use core::fmt;
+use digest::consts::{U8, U16, U32};
+use digest::{FixedOutput, FixedOutputReset, Output, OutputSizeUser, Reset, Update};
+
+macro_rules! city_digest {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $size:ty, $seed:ty, |$bytes:ident, $seed_var:ident| $hash:expr
+    ) => {
+        $(#[$doc])*
+        #[derive(Clone, Default)]
+        pub struct $name {
+            bytes: Vec<u8>,
+            seed: Option<$seed>,
+        }
+
+        impl $name {
+            /// Creates an empty, unseeded digest.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Creates an empty digest hashing with `seed`.
+            ///
+            /// [`Reset`] keeps the seed.
+            pub fn new_with_seed(seed: $seed) -> Self {
+                Self {
+                    bytes: Vec::new(),
+                    seed: Some(seed),
+                }
+            }
+
+            fn hash(&self, out: &mut Output<Self>) {
+                let $bytes = self.bytes.as_slice();
+                let $seed_var = self.seed;
+                let words: &[u64] = &$hash;
+                for (chunk, word) in out.chunks_exact_mut(8).zip(words) {
+                    chunk.copy_from_slice(&word.to_le_bytes());
+                }
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(concat!(stringify!($name), " { ... }"))
+            }
+        }
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $size;
+        }
+
+        impl Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                self.bytes.extend_from_slice(data);
+            }
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                self.hash(out);
+            }
+        }
+
+        impl FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                self.hash(out);
+                self.reset();
+            }
+        }
+
+        impl Reset for $name {
+            fn reset(&mut self) {
+                self.bytes.clear();
+            }
+        }
+    };
+}
+
+fn halves(x: u128) -> [u64; 2] {
+    [x as u64, (x >> 64) as u64]
+}
+
+city_digest!(
+    /// [`city_hash64`] and [`city_hash64_with_seed`] as an 8-byte digest.
+    ///
+    /// Not cryptographic; see the [module documentation](self).
+    City64, U8, u64, |bytes, seed| [match seed {
+        Some(seed) => city_hash64_with_seed(bytes, seed),
+        None => city_hash64(bytes),
+    }]
+);
+
+city_digest!(
+    /// [`city_hash128`] and [`city_hash128_with_seed`] as a 16-byte digest.
+    ///
+    /// Not cryptographic; see the [module documentation](self).
+    City128, U16, u128, |bytes, seed| halves(match seed {
+        Some(seed) => city_hash128_with_seed(bytes, seed),
+        None => city_hash128(bytes),
+    })
+);
+
+city_digest!(
+    /// [`city_hash256_crc`](crate::city_hash256_crc) and
+    /// [`city_hash256_crc_with_seed`] as a 32-byte digest.
+    ///
+    /// Not cryptographic; see the [module documentation](self).
+    City256Crc, U32, u32, |bytes, seed| {
+        let hash = city_hash256_crc_with_seed(bytes, seed.unwrap_or(0));
+        [halves(hash.lo), halves(hash.hi)].concat()
+    }
+);
+
+city_digest!(
+    /// [`city_murmur`] and [`city_murmur_with_seed`] as a 16-byte digest.
+    ///
+    /// Not cryptographic; see the [module documentation](self).
+    CityMurmur, U16, u128, |bytes, seed| halves(match seed {
+        Some(seed) => city_murmur_with_seed(bytes, seed),
+        None => city_murmur(bytes),
+    })
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city_test::city_test_bytes;
+    use crate::{city_hash_crc256_words, city_hash256_crc};
+    use digest::dev::fixed_test;
+
+    fn le(words: &[u64]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_output_sizes() {
+        assert_eq!(City64::output_size(), 8);
+        assert_eq!(City128::output_size(), 16);
+        assert_eq!(City256Crc::output_size(), 32);
+        assert_eq!(CityMurmur::output_size(), 16);
+    }
+
+    #[test]
+    fn test_digests() {
+        let data = city_test_bytes(4 * 4096);
+        for len in (0..=300).chain([1023, 1024, 4096]) {
+            let input = &data[len * 3..len * 4];
+            let output = le(&[city_hash64(input)]);
+            assert_eq!(fixed_test::<City64>(input, &output), None, "{len}");
+            let output = le(&halves(city_hash128(input)));
+            assert_eq!(fixed_test::<City128>(input, &output), None, "{len}");
+            let output = le(&city_hash_crc256_words(input));
+            assert_eq!(fixed_test::<City256Crc>(input, &output), None, "{len}");
+            let output = le(&halves(city_murmur(input)));
+            assert_eq!(fixed_test::<CityMurmur>(input, &output), None, "{len}");
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let data = city_test_bytes(300);
+        let mut hasher = City128::new();
+        hasher.update(b"discarded");
+        hasher.reset();
+        for chunk in data.chunks(7) {
+            hasher.update(chunk);
+        }
+        let output = le(&halves(city_hash128(&data)));
+        assert_eq!(hasher.finalize_fixed_reset()[..], output[..]);
+        hasher.update(&data);
+        assert_eq!(hasher.finalize_fixed_reset()[..], output[..]);
+        assert_eq!(
+            hasher.finalize_fixed()[..],
+            le(&halves(city_hash128(b"")))[..]
+        );
+    }
+
+    #[test]
+    fn test_seeded_digests() {
+        let data = city_test_bytes(1000);
+        for len in [0, 7, 16, 33, 64, 127, 128, 240, 1000] {
+            let input = &data[..len];
+            let digest = City64::new_with_seed(17).chain(input).finalize_fixed();
+            assert_eq!(digest[..], le(&[city_hash64_with_seed(input, 17)]));
+            let digest = City128::new_with_seed(5 << 70 | 9)
+                .chain(input)
+                .finalize_fixed();
+            assert_eq!(
+                digest[..],
+                le(&halves(city_hash128_with_seed(input, 5 << 70 | 9)))
+            );
+            let digest = CityMurmur::new_with_seed(3).chain(input).finalize_fixed();
+            assert_eq!(digest[..], le(&halves(city_murmur_with_seed(input, 3))));
+            let hash = city_hash256_crc_with_seed(input, 11);
+            let digest = City256Crc::new_with_seed(11).chain(input).finalize_fixed();
+            assert_eq!(digest[..], le(&[halves(hash.lo), halves(hash.hi)].concat()));
+
+            let mut seeded = City64::new_with_seed(17);
+            seeded.update(b"discarded");
+            seeded.reset();
+            seeded.update(input);
+            assert_eq!(
+                seeded.finalize_fixed()[..],
+                le(&[city_hash64_with_seed(input, 17)])
+            );
+        }
+        let hash = city_hash256_crc(b"abc");
+        let digest = City256Crc::new().chain(b"abc").finalize_fixed();
+        assert_eq!(digest[..16], hash.lo.to_le_bytes());
+        assert_eq!(digest[16..], hash.hi.to_le_bytes());
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! `farmhashcc`, FarmHash's copy of CityHash32 and CityHash128 v1.1.
+use super::mk::hash32_with_seed_impl;
+use crate::city32::city_hash32;
+use crate::city128::city_hash128;
+
+/// Computes `farmhashcc::Hash32`, which is [`city_hash32`].
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 32-bit hash of `bytes`.
+pub fn hash32(bytes: &[u8]) -> u32 {
+    city_hash32(bytes)
+}
+
+/// Computes `farmhashcc::Hash32WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 32-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 32-bit hash of `bytes` and `seed`.
+pub fn hash32_with_seed(bytes: &[u8], seed: u32) -> u32 {
+    hash32_with_seed_impl(bytes, seed, city_hash32)
+}
+
+/// Computes `farmhashcc::Fingerprint128`, which is [`city_hash128`].
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 128-bit hash with `Uint128Low64` in the low 64 bits.
+pub fn fingerprint128(bytes: &[u8]) -> u128 {
+    city_hash128(bytes)
+}
+
+/// Computes `farmhashcc::CityHash128WithSeed`, which is
+/// [`city_hash128_with_seed`](crate::city_hash128_with_seed).
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 128-bit seed; the low half is `Uint128Low64(seed)`.
+///
+/// # Returns
+///
+/// The 128-bit hash with `Uint128Low64` in the low 64 bits.
+pub fn city_hash128_with_seed(bytes: &[u8], seed: u128) -> u128 {
+    crate::city128::city_hash128_with_seed(bytes, seed)
+}
@@ -0,0 +1,180 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! `farmhashmk`, the 32-bit FarmHash behind `Fingerprint32` and the portable
+//! `util::Hash32`.
+use crate::city32::{C1, C2, fmix, mur, read_le32};
+
+#[inline]
+fn hash32_len13to24(bytes: &[u8], seed: u32) -> u32 {
+    let len = bytes.len();
+    let mut a = read_le32(bytes, (len >> 1) - 4);
+    let b = read_le32(bytes, 4);
+    let c = read_le32(bytes, len - 8);
+    let d = read_le32(bytes, len >> 1);
+    let e = read_le32(bytes, 0);
+    let f = read_le32(bytes, len - 4);
+    let mut h = d
+        .wrapping_mul(C1)
+        .wrapping_add(len as u32)
+        .wrapping_add(seed);
+    a = a.rotate_right(12).wrapping_add(f);
+    h = mur(c, h).wrapping_add(a);
+    a = a.rotate_right(3).wrapping_add(c);
+    h = mur(e, h).wrapping_add(a);
+    a = a.wrapping_add(f).rotate_right(12).wrapping_add(d);
+    h = mur(b ^ seed, h).wrapping_add(a);
+    fmix(h)
+}
+
+#[inline]
+fn hash32_len0to4(bytes: &[u8], seed: u32) -> u32 {
+    let mut b = seed;
+    let mut c = 9u32;
+    for &byte in bytes {
+        b = b.wrapping_mul(C1).wrapping_add(byte as i8 as u32);
+        c ^= b;
+    }
+    fmix(mur(b, mur(bytes.len() as u32, c)))
+}
+
+#[inline]
+fn hash32_len5to12(bytes: &[u8], seed: u32) -> u32 {
+    let len = bytes.len();
+    let a = (len as u32).wrapping_add(read_le32(bytes, 0));
+    let b = (len as u32 * 5).wrapping_add(read_le32(bytes, len - 4));
+    let c = 9u32.wrapping_add(read_le32(bytes, (len >> 1) & 4));
+    let d = (len as u32 * 5).wrapping_add(seed);
+    fmix(seed ^ mur(c, mur(b, mur(a, d))))
+}
+
+#[inline]
+fn step(h: u32, value: u32) -> u32 {
+    (h ^ value)
+        .rotate_right(19)
+        .wrapping_mul(5)
+        .wrapping_add(0xe6546b64)
+}
+
+#[inline]
+fn premix(value: u32) -> u32 {
+    value.wrapping_mul(C1).rotate_right(17).wrapping_mul(C2)
+}
+
+/// Computes `farmhashmk::Hash32`, which is also `Fingerprint32`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 32-bit hash of `bytes`.
+pub fn hash32(bytes: &[u8]) -> u32 {
+    let len = bytes.len();
+    if len <= 24 {
+        return if len <= 4 {
+            hash32_len0to4(bytes, 0)
+        } else if len <= 12 {
+            hash32_len5to12(bytes, 0)
+        } else {
+            hash32_len13to24(bytes, 0)
+        };
+    }
+
+    // len > 24
+    let mut h = len as u32;
+    let mut g = C1.wrapping_mul(len as u32);
+    let mut f = g;
+    let a0 = premix(read_le32(bytes, len - 4));
+    let a1 = premix(read_le32(bytes, len - 8));
+    let a2 = premix(read_le32(bytes, len - 16));
+    let a3 = premix(read_le32(bytes, len - 12));
+    let a4 = premix(read_le32(bytes, len - 20));
+    h = step(step(h, a0), a2);
+    g = step(step(g, a1), a3);
+    f = f.wrapping_add(a4).rotate_right(19).wrapping_add(113);
+    let iters = (len - 1) / 20;
+    for chunk in bytes[..iters * 20].chunks_exact(20) {
+        let a = read_le32(chunk, 0);
+        let b = read_le32(chunk, 4);
+        let c = read_le32(chunk, 8);
+        let d = read_le32(chunk, 12);
+        let e = read_le32(chunk, 16);
+        h = h.wrapping_add(a);
+        g = g.wrapping_add(b);
+        f = f.wrapping_add(c);
+        h = mur(d, h).wrapping_add(e);
+        g = mur(c, g).wrapping_add(a);
+        f = mur(b.wrapping_add(e.wrapping_mul(C1)), f).wrapping_add(d);
+        f = f.wrapping_add(g);
+        g = g.wrapping_add(f);
+    }
+    g = g.rotate_right(11).wrapping_mul(C1);
+    g = g.rotate_right(17).wrapping_mul(C1);
+    f = f.rotate_right(11).wrapping_mul(C1);
+    f = f.rotate_right(17).wrapping_mul(C1);
+    h = h.wrapping_add(g).rotate_right(19);
+    h = h.wrapping_mul(5).wrapping_add(0xe6546b64);
+    h = h.rotate_right(17).wrapping_mul(C1);
+    h = h.wrapping_add(f).rotate_right(19);
+    h = h.wrapping_mul(5).wrapping_add(0xe6546b64);
+    h.rotate_right(17).wrapping_mul(C1)
+}
+
+/// Seeded 32-bit hash shared by `farmhashmk` and `farmhashcc`, which differ
+/// only in the unseeded `hash32` applied past the first 24 bytes.
+#[inline]
+pub(super) fn hash32_with_seed_impl(bytes: &[u8], seed: u32, hash32: fn(&[u8]) -> u32) -> u32 {
+    let len = bytes.len();
+    if len <= 24 {
+        return if len >= 13 {
+            hash32_len13to24(bytes, seed.wrapping_mul(C1))
+        } else if len >= 5 {
+            hash32_len5to12(bytes, seed)
+        } else {
+            hash32_len0to4(bytes, seed)
+        };
+    }
+    let h = hash32_len13to24(&bytes[..24], seed ^ len as u32);
+    mur(hash32(&bytes[24..]).wrapping_add(seed), h)
+}
+
+/// Computes `farmhashmk::Hash32WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 32-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 32-bit hash of `bytes` and `seed`.
+pub fn hash32_with_seed(bytes: &[u8], seed: u32) -> u32 {
+    hash32_with_seed_impl(bytes, seed, hash32)
+}
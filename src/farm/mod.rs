@@ -0,0 +1,350 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! FarmHash, the successor of CityHash built on the same primitives.
+//!
+//! The fingerprint functions are frozen: the same input gives the same value on
+//! every platform and in every release. [`fingerprint64`] is what BigQuery's
+//! `FARM_FINGERPRINT` and Spanner compute, reinterpreted there as a signed
+//! `INT64`.
+//!
+//! The portable variants of the hash family live in [`na`], [`uo`], [`xo`],
+//! [`mk`] and [`cc`], named after the C++ namespaces. The `hash*` functions of
+//! this module pick among them like the C++ `util::Hash*` functions do when
+//! built without SSE4.1/SSE4.2 and with `NDEBUG`; C++ builds that use the
+//! SIMD variants (`farmhashte`, `nt`, `sa`, `su`) return different values for
+//! `util::Hash32` and `util::Hash64`.
+pub mod cc;
+pub mod mk;
+pub mod na;
+pub mod uo;
+pub mod xo;
+
+use crate::city128::city_hash128;
+
+/// Computes the portable `util::Hash32`, which is `farmhashmk::Hash32`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 32-bit hash of `bytes`.
+pub fn hash32(bytes: &[u8]) -> u32 {
+    mk::hash32(bytes)
+}
+
+/// Computes the portable `util::Hash32WithSeed`, which is `farmhashmk::Hash32WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 32-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 32-bit hash of `bytes` and `seed`.
+pub fn hash32_with_seed(bytes: &[u8], seed: u32) -> u32 {
+    mk::hash32_with_seed(bytes, seed)
+}
+
+/// Computes the portable `util::Hash64`, which is `farmhashxo::Hash64`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes`.
+pub fn hash64(bytes: &[u8]) -> u64 {
+    xo::hash64(bytes)
+}
+
+/// Computes `util::Hash64WithSeed`, which is `farmhashna::Hash64WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 64-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and `seed`.
+pub fn hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    na::hash64_with_seed(bytes, seed)
+}
+
+/// Computes `util::Hash64WithSeeds`, which is `farmhashna::Hash64WithSeeds`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed0` - The first 64-bit seed.
+/// * `seed1` - The second 64-bit seed.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and both seeds.
+pub fn hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
+    na::hash64_with_seeds(bytes, seed0, seed1)
+}
+
+/// Computes `util::Hash128`, which is `farmhashcc::Fingerprint128`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 128-bit hash with `Uint128Low64` in the low 64 bits.
+pub fn hash128(bytes: &[u8]) -> u128 {
+    cc::fingerprint128(bytes)
+}
+
+/// Computes `util::Hash128WithSeed`, which is `farmhashcc::CityHash128WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 128-bit seed; the low half is `Uint128Low64(seed)`.
+///
+/// # Returns
+///
+/// The 128-bit hash with `Uint128Low64` in the low 64 bits.
+pub fn hash128_with_seed(bytes: &[u8], seed: u128) -> u128 {
+    cc::city_hash128_with_seed(bytes, seed)
+}
+
+/// Computes FarmHash `Fingerprint64`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to fingerprint.
+///
+/// # Returns
+///
+/// The 64-bit fingerprint; `fingerprint64(s) as i64` equals BigQuery's
+/// `FARM_FINGERPRINT(s)` for the UTF-8 bytes of a `STRING` or for a `BYTES` value.
+pub fn fingerprint64(bytes: &[u8]) -> u64 {
+    na::hash64(bytes)
+}
+
+/// Computes FarmHash `Fingerprint32`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to fingerprint.
+///
+/// # Returns
+///
+/// The 32-bit fingerprint.
+pub fn fingerprint32(bytes: &[u8]) -> u32 {
+    mk::hash32(bytes)
+}
+
+/// Computes FarmHash `Fingerprint128`.
+///
+/// FarmHash defines it as CityHash128 v1.1, so it equals
+/// [`city_hash128`].
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to fingerprint.
+///
+/// # Returns
+///
+/// The 128-bit fingerprint with `Uint128Low64` in the low 64 bits.
+pub fn fingerprint128(bytes: &[u8]) -> u128 {
+    city_hash128(bytes)
+}
+
+#[cfg(test)]
+pub(crate) mod self_test {
+    /// The `(offset, len)` of every self-test row, in table order.
+    pub(crate) fn inputs() -> Vec<(usize, usize)> {
+        const DATA_SIZE: usize = 1 << 20;
+        let mut rows: Vec<(usize, usize)> = (0..299).map(|i| (i * i, i)).collect();
+        let mut i = 299;
+        while i < DATA_SIZE {
+            rows.push((0, i));
+            i += i / 7;
+        }
+        rows.push((0, DATA_SIZE));
+        rows
+    }
+
+    /// `CreateSeed(offset, salt)` of the self-test.
+    pub(crate) fn seed(offset: usize, salt: i32) -> u32 {
+        use crate::city32::C1;
+        let mix = |mut h: u32| {
+            for _ in 0..3 {
+                h = h.wrapping_mul(C1);
+                h ^= h >> 17;
+            }
+            h
+        };
+        mix(mix(salt as u32).wrapping_add(offset as u32))
+    }
+
+    /// The table of one namespace, as hex words per row.
+    pub(crate) fn table(text: &str) -> Vec<Vec<u64>> {
+        text.lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|word| u64::from_str_radix(word, 16).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::city_test::city_test_bytes;
+
+    /// Runs `check(row, bytes, seed, seed0, seed1)` over every self-test input.
+    fn for_each_input(mut check: impl FnMut(usize, &[u8], u32, u32, u32)) {
+        let mut inputs = self_test::inputs();
+        assert_eq!(inputs.len(), 362);
+        if cfg!(miri) {
+            // As in `city_test`, Miri checks only the rows up to 298 bytes.
+            inputs.truncate(299);
+        }
+        // The self-test hashes the same data as `city-test.cc`.
+        let end = inputs.iter().map(|&(offset, len)| offset + len).max();
+        let data = city_test_bytes(end.unwrap());
+        for (row, &(offset, len)) in inputs.iter().enumerate() {
+            check(
+                row,
+                &data[offset..offset + len],
+                self_test::seed(offset, -1),
+                self_test::seed(offset, 0),
+                self_test::seed(offset, 1),
+            );
+        }
+    }
+
+    #[test]
+    fn test_self_test_na() {
+        let table = self_test::table(include_str!("../../testdata/farmhash_na.txt"));
+        for_each_input(|row, bytes, seed, seed0, seed1| {
+            let actual = [
+                na::hash64_with_seeds(bytes, seed0 as u64, seed1 as u64),
+                na::hash64_with_seed(bytes, seed as u64),
+                na::hash64(bytes),
+            ];
+            assert_eq!(actual[..], table[row][..], "row {row}");
+        });
+    }
+
+    #[test]
+    fn test_self_test_uo() {
+        let table = self_test::table(include_str!("../../testdata/farmhash_uo.txt"));
+        for_each_input(|row, bytes, seed, _, _| {
+            let actual = [uo::hash64_with_seed(bytes, seed as u64), uo::hash64(bytes)];
+            assert_eq!(actual[..], table[row][..], "row {row}");
+        });
+    }
+
+    #[test]
+    fn test_self_test_xo() {
+        let table = self_test::table(include_str!("../../testdata/farmhash_xo.txt"));
+        for_each_input(|row, bytes, seed, seed0, seed1| {
+            let actual = [
+                xo::hash64_with_seeds(bytes, seed0 as u64, seed1 as u64),
+                xo::hash64_with_seed(bytes, seed as u64),
+                xo::hash64(bytes),
+            ];
+            assert_eq!(actual[..], table[row][..], "row {row}");
+        });
+    }
+
+    #[test]
+    fn test_self_test_mk() {
+        let table = self_test::table(include_str!("../../testdata/farmhash_mk.txt"));
+        for_each_input(|row, bytes, seed, _, _| {
+            let actual = [
+                mk::hash32_with_seed(bytes, seed) as u64,
+                mk::hash32(bytes) as u64,
+            ];
+            assert_eq!(actual[..], table[row][..], "row {row}");
+        });
+    }
+
+    #[test]
+    fn test_self_test_cc() {
+        let table = self_test::table(include_str!("../../testdata/farmhash_cc.txt"));
+        for_each_input(|row, bytes, seed, seed0, seed1| {
+            let h128 = cc::fingerprint128(bytes);
+            let s128 = cc::city_hash128_with_seed(bytes, seed0 as u128 | (seed1 as u128) << 64);
+            let actual = [
+                cc::hash32_with_seed(bytes, seed) as u64,
+                cc::hash32(bytes) as u64,
+                h128 as u64,
+                (h128 >> 64) as u64,
+                s128 as u64,
+                (s128 >> 64) as u64,
+            ];
+            assert_eq!(actual[..], table[row][..], "row {row}");
+        });
+    }
+
+    #[test]
+    fn test_fingerprints_and_portable_hashes() {
+        for_each_input(|_, bytes, seed, seed0, seed1| {
+            assert_eq!(fingerprint64(bytes), na::hash64(bytes));
+            assert_eq!(fingerprint32(bytes), mk::hash32(bytes));
+            assert_eq!(fingerprint128(bytes), cc::fingerprint128(bytes));
+            assert_eq!(hash32(bytes), mk::hash32(bytes));
+            assert_eq!(
+                hash32_with_seed(bytes, seed),
+                mk::hash32_with_seed(bytes, seed)
+            );
+            assert_eq!(hash64(bytes), xo::hash64(bytes));
+            assert_eq!(
+                hash64_with_seeds(bytes, seed0 as u64, seed1 as u64),
+                na::hash64_with_seeds(bytes, seed0 as u64, seed1 as u64)
+            );
+        });
+    }
+
+    #[test]
+    fn test_bigquery_farm_fingerprint() {
+        // FARM_FINGERPRINT of the empty string and of the rows of the BigQuery
+        // documentation example, CONCAT(CAST(x AS STRING), y, CAST(z AS STRING)).
+        assert_eq!(fingerprint64(b"") as i64, -7286425919675154353);
+        assert_eq!(fingerprint64(b"1footrue") as i64, -1541654101129638711);
+        assert_eq!(fingerprint64(b"2applefalse") as i64, 2794438866806483259);
+        assert_eq!(fingerprint64(b"3true") as i64, -4880158226897771312);
+    }
+}
@@ -0,0 +1,193 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! `farmhashna`, the 64-bit FarmHash behind `Fingerprint64` and the seeded
+//! `util::Hash64WithSeed(s)`.
+use crate::city64::{
+    K0, K1, K2, hash_len_16_u64, hash_len_16_with_mul, hash64_len_0_to_16, hash64_len_17_to_32,
+    read_le64, shift_mix, weak_hash_len_32_with_seeds,
+};
+
+#[inline]
+fn hash_len_33_to_64(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    let mul = K2.wrapping_add(len as u64 * 2);
+    let a = read_le64(bytes, 0).wrapping_mul(K2);
+    let b = read_le64(bytes, 8);
+    let c = read_le64(bytes, len - 8).wrapping_mul(mul);
+    let d = read_le64(bytes, len - 16).wrapping_mul(K2);
+    let y = a
+        .wrapping_add(b)
+        .rotate_right(43)
+        .wrapping_add(c.rotate_right(30))
+        .wrapping_add(d);
+    let z = hash_len_16_with_mul(
+        y,
+        a.wrapping_add(b.wrapping_add(K2).rotate_right(18))
+            .wrapping_add(c),
+        mul,
+    );
+    let e = read_le64(bytes, 16).wrapping_mul(mul);
+    let f = read_le64(bytes, 24);
+    let g = y.wrapping_add(read_le64(bytes, len - 32)).wrapping_mul(mul);
+    let h = z.wrapping_add(read_le64(bytes, len - 24)).wrapping_mul(mul);
+    hash_len_16_with_mul(
+        e.wrapping_add(f)
+            .rotate_right(43)
+            .wrapping_add(g.rotate_right(30))
+            .wrapping_add(h),
+        e.wrapping_add(f.wrapping_add(a).rotate_right(18))
+            .wrapping_add(g),
+        mul,
+    )
+}
+
+/// One 64-byte round of the main loop, with `mul` and the `w.1` factor of the
+/// final round as parameters.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+fn round(
+    chunk: &[u8],
+    mul: u64,
+    w1_mul: u64,
+    x: &mut u64,
+    y: &mut u64,
+    z: &mut u64,
+    v: &mut (u64, u64),
+    w: &mut (u64, u64),
+) {
+    *x = x
+        .wrapping_add(*y)
+        .wrapping_add(v.0)
+        .wrapping_add(read_le64(chunk, 8))
+        .rotate_right(37)
+        .wrapping_mul(mul);
+    *y = y
+        .wrapping_add(v.1)
+        .wrapping_add(read_le64(chunk, 48))
+        .rotate_right(42)
+        .wrapping_mul(mul);
+    *x ^= w.1.wrapping_mul(w1_mul);
+    *y = y.wrapping_add(v.0.wrapping_mul(w1_mul).wrapping_add(read_le64(chunk, 40)));
+    *z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(mul);
+    *v = weak_hash_len_32_with_seeds(chunk, 0, v.1.wrapping_mul(mul), x.wrapping_add(w.0));
+    *w = weak_hash_len_32_with_seeds(
+        chunk,
+        32,
+        z.wrapping_add(w.1),
+        y.wrapping_add(read_le64(chunk, 16)),
+    );
+    std::mem::swap(z, x);
+}
+
+/// Computes `farmhashna::Hash64`, which is also `Fingerprint64`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes`.
+pub fn hash64(bytes: &[u8]) -> u64 {
+    const SEED: u64 = 81;
+    let len = bytes.len();
+    if len <= 16 {
+        return hash64_len_0_to_16(bytes);
+    } else if len <= 32 {
+        return hash64_len_17_to_32(bytes);
+    } else if len <= 64 {
+        return hash_len_33_to_64(bytes);
+    }
+
+    // For strings over 64 bytes we loop.  Internal state consists of
+    // 56 bytes: v, w, x, y, and z.
+    let mut x = SEED;
+    let mut y = SEED.wrapping_mul(K1).wrapping_add(113);
+    let mut z = shift_mix(y.wrapping_mul(K2).wrapping_add(113)).wrapping_mul(K2);
+    let mut v = (0u64, 0u64);
+    let mut w = (0u64, 0u64);
+    x = x.wrapping_mul(K2).wrapping_add(read_le64(bytes, 0));
+
+    // Stop so that after the loop we have 1 to 64 bytes left to process.
+    let end = (len - 1) / 64 * 64;
+    for chunk in bytes[..end].chunks_exact(64) {
+        round(chunk, K1, 1, &mut x, &mut y, &mut z, &mut v, &mut w);
+    }
+    let mul = K1.wrapping_add((z & 0xff) << 1);
+    // Process the last 64 bytes of input.
+    w.0 = w.0.wrapping_add(((len - 1) & 63) as u64);
+    v.0 = v.0.wrapping_add(w.0);
+    w.0 = w.0.wrapping_add(v.0);
+    round(
+        &bytes[len - 64..],
+        mul,
+        9,
+        &mut x,
+        &mut y,
+        &mut z,
+        &mut v,
+        &mut w,
+    );
+    hash_len_16_with_mul(
+        hash_len_16_with_mul(v.0, w.0, mul)
+            .wrapping_add(shift_mix(y).wrapping_mul(K0))
+            .wrapping_add(z),
+        hash_len_16_with_mul(v.1, w.1, mul).wrapping_add(x),
+        mul,
+    )
+}
+
+/// Computes `farmhashna::Hash64WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 64-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and `seed`.
+pub fn hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    hash64_with_seeds(bytes, K2, seed)
+}
+
+/// Computes `farmhashna::Hash64WithSeeds`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed0` - Subtracted from the unseeded hash.
+/// * `seed1` - Mixed with the difference.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and both seeds.
+pub fn hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
+    hash_len_16_u64(hash64(bytes).wrapping_sub(seed0), seed1)
+}
@@ -0,0 +1,181 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! `farmhashuo`, the 64-bit FarmHash used by `farmhashxo` for inputs over 256 bytes.
+use super::na;
+use crate::city64::{K2, hash_len_16_with_mul, read_le64, shift_mix, weak_hash_len_32_with_seeds};
+
+#[inline]
+fn h(x: u64, y: u64, mul: u64, r: u32) -> u64 {
+    let mut a = (x ^ y).wrapping_mul(mul);
+    a ^= a >> 47;
+    let b = (y ^ a).wrapping_mul(mul);
+    b.rotate_right(r).wrapping_mul(mul)
+}
+
+/// Computes `farmhashuo::Hash64WithSeeds`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed0` - The first 64-bit seed.
+/// * `seed1` - The second 64-bit seed.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and both seeds.
+pub fn hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
+    let len = bytes.len();
+    if len <= 64 {
+        return na::hash64_with_seeds(bytes, seed0, seed1);
+    }
+
+    // For strings over 64 bytes we loop.  Internal state consists of
+    // 64 bytes: u, v, w, x, y, and z.
+    let mut x = seed0;
+    let mut y = seed1.wrapping_mul(K2).wrapping_add(113);
+    let mut z = shift_mix(y.wrapping_mul(K2)).wrapping_mul(K2);
+    let mut v = (seed0, seed1);
+    let mut w = (0u64, 0u64);
+    let mut u = x.wrapping_sub(z);
+    x = x.wrapping_mul(K2);
+    let mul = K2.wrapping_add(u & 0x82);
+
+    // Stop so that after the loop we have 1 to 64 bytes left to process.
+    let end = (len - 1) / 64 * 64;
+    for s in bytes[..end].chunks_exact(64) {
+        let a0 = read_le64(s, 0);
+        let a1 = read_le64(s, 8);
+        let a2 = read_le64(s, 16);
+        let a3 = read_le64(s, 24);
+        let a4 = read_le64(s, 32);
+        let a5 = read_le64(s, 40);
+        let a6 = read_le64(s, 48);
+        let a7 = read_le64(s, 56);
+        x = x.wrapping_add(a0.wrapping_add(a1));
+        y = y.wrapping_add(a2);
+        z = z.wrapping_add(a3);
+        v.0 = v.0.wrapping_add(a4);
+        v.1 = v.1.wrapping_add(a5.wrapping_add(a1));
+        w.0 = w.0.wrapping_add(a6);
+        w.1 = w.1.wrapping_add(a7);
+
+        x = x.rotate_right(26).wrapping_mul(9);
+        y = y.rotate_right(29);
+        z = z.wrapping_mul(mul);
+        v.0 = v.0.rotate_right(33);
+        v.1 = v.1.rotate_right(30);
+        w.0 = (w.0 ^ x).wrapping_mul(9);
+        z = z.rotate_right(32).wrapping_add(w.1);
+        w.1 = w.1.wrapping_add(z);
+        z = z.wrapping_mul(9);
+        std::mem::swap(&mut u, &mut y);
+
+        z = z.wrapping_add(a0.wrapping_add(a6));
+        v.0 = v.0.wrapping_add(a2);
+        v.1 = v.1.wrapping_add(a3);
+        w.0 = w.0.wrapping_add(a4);
+        w.1 = w.1.wrapping_add(a5.wrapping_add(a6));
+        x = x.wrapping_add(a1);
+        y = y.wrapping_add(a7);
+
+        y = y.wrapping_add(v.0);
+        v.0 = v.0.wrapping_add(x.wrapping_sub(y));
+        v.1 = v.1.wrapping_add(w.0);
+        w.0 = w.0.wrapping_add(v.1);
+        w.1 = w.1.wrapping_add(x.wrapping_sub(y));
+        x = x.wrapping_add(w.1);
+        w.1 = w.1.rotate_right(34);
+        std::mem::swap(&mut u, &mut z);
+    }
+    // Process the last 64 bytes of input.
+    let s = &bytes[len - 64..];
+    u = u.wrapping_mul(9);
+    v.1 = v.1.rotate_right(28);
+    v.0 = v.0.rotate_right(20);
+    w.0 = w.0.wrapping_add(((len - 1) & 63) as u64);
+    u = u.wrapping_add(y);
+    y = y.wrapping_add(u);
+    x = y
+        .wrapping_sub(x)
+        .wrapping_add(v.0)
+        .wrapping_add(read_le64(s, 8))
+        .rotate_right(37)
+        .wrapping_mul(mul);
+    y = (y ^ v.1 ^ read_le64(s, 48))
+        .rotate_right(42)
+        .wrapping_mul(mul);
+    x ^= w.1.wrapping_mul(9);
+    y = y.wrapping_add(v.0.wrapping_add(read_le64(s, 40)));
+    z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(mul);
+    v = weak_hash_len_32_with_seeds(s, 0, v.1.wrapping_mul(mul), x.wrapping_add(w.0));
+    w = weak_hash_len_32_with_seeds(s, 32, z.wrapping_add(w.1), y.wrapping_add(read_le64(s, 16)));
+    h(
+        hash_len_16_with_mul(v.0.wrapping_add(x), w.0 ^ y, mul)
+            .wrapping_add(z)
+            .wrapping_sub(u),
+        h(v.1.wrapping_add(y), w.1.wrapping_add(z), K2, 30) ^ x,
+        K2,
+        31,
+    )
+}
+
+/// Computes `farmhashuo::Hash64WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 64-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and `seed`.
+pub fn hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    if bytes.len() <= 64 {
+        na::hash64_with_seed(bytes, seed)
+    } else {
+        hash64_with_seeds(bytes, 0, seed)
+    }
+}
+
+/// Computes `farmhashuo::Hash64`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes`.
+pub fn hash64(bytes: &[u8]) -> u64 {
+    if bytes.len() <= 64 {
+        na::hash64(bytes)
+    } else {
+        hash64_with_seeds(bytes, 81, 0)
+    }
+}
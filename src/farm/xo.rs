@@ -0,0 +1,131 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! `farmhashxo`, the 64-bit FarmHash behind the portable `util::Hash64`.
+use super::{na, uo};
+use crate::city64::{K1, K2, hash64_len_0_to_16, hash64_len_17_to_32, read_le64, shift_mix};
+
+#[inline]
+fn h32(bytes: &[u8], mul: u64, seed0: u64, seed1: u64) -> u64 {
+    let len = bytes.len();
+    let a = read_le64(bytes, 0).wrapping_mul(K1);
+    let b = read_le64(bytes, 8);
+    let c = read_le64(bytes, len - 8).wrapping_mul(mul);
+    let d = read_le64(bytes, len - 16).wrapping_mul(K2);
+    let u = a
+        .wrapping_add(b)
+        .rotate_right(43)
+        .wrapping_add(c.rotate_right(30))
+        .wrapping_add(d)
+        .wrapping_add(seed0);
+    let v = a
+        .wrapping_add(b.wrapping_add(K2).rotate_right(18))
+        .wrapping_add(c)
+        .wrapping_add(seed1);
+    let a = shift_mix((u ^ v).wrapping_mul(mul));
+    shift_mix((v ^ a).wrapping_mul(mul))
+}
+
+#[inline]
+fn hash_len_33_to_64(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    let mul0 = K2.wrapping_sub(30);
+    let mul1 = mul0.wrapping_add(2 * len as u64);
+    let h0 = h32(&bytes[..32], mul0, 0, 0);
+    let h1 = h32(&bytes[len - 32..], mul1, 0, 0);
+    h1.wrapping_mul(mul1).wrapping_add(h0).wrapping_mul(mul1)
+}
+
+#[inline]
+fn hash_len_65_to_96(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    let mul0 = K2.wrapping_sub(114);
+    let mul1 = mul0.wrapping_add(2 * len as u64);
+    let h0 = h32(&bytes[..32], mul0, 0, 0);
+    let h1 = h32(&bytes[32..64], mul1, 0, 0);
+    let h2 = h32(&bytes[len - 32..], mul1, h0, h1);
+    h2.wrapping_mul(9)
+        .wrapping_add(h0 >> 17)
+        .wrapping_add(h1 >> 21)
+        .wrapping_mul(mul1)
+}
+
+/// Computes `farmhashxo::Hash64`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes`.
+pub fn hash64(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    if len <= 16 {
+        hash64_len_0_to_16(bytes)
+    } else if len <= 32 {
+        hash64_len_17_to_32(bytes)
+    } else if len <= 64 {
+        hash_len_33_to_64(bytes)
+    } else if len <= 96 {
+        hash_len_65_to_96(bytes)
+    } else if len <= 256 {
+        na::hash64(bytes)
+    } else {
+        uo::hash64(bytes)
+    }
+}
+
+/// Computes `farmhashxo::Hash64WithSeed`, which is `farmhashuo::Hash64WithSeed`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 64-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and `seed`.
+pub fn hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    uo::hash64_with_seed(bytes, seed)
+}
+
+/// Computes `farmhashxo::Hash64WithSeeds`, which is `farmhashuo::Hash64WithSeeds`.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed0` - The first 64-bit seed.
+/// * `seed1` - The second 64-bit seed.
+///
+/// # Returns
+///
+/// The 64-bit hash of `bytes` and both seeds.
+pub fn hash64_with_seeds(bytes: &[u8], seed0: u64, seed1: u64) -> u64 {
+    uo::hash64_with_seeds(bytes, seed0, seed1)
+}
@@ -0,0 +1,336 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Hashing of files on disk.
+//!
+//! [`hash_file`] gives the same result as reading the whole file into memory
+//! and calling the matching one-shot function. Regular files are memory-mapped
+//! with the `memmap2` feature and read in one go otherwise, including when
+//! `portable` is enabled as well, since mapping needs `unsafe`; pipes,
+//! character devices and other special files are streamed.
+use crate::city32::city_hash32;
+use crate::city64::city_hash64;
+use crate::city128::{city_hash128, city_hash128_crc};
+use crate::city256::{Hash256, city_hash256_crc};
+use crate::crc32c::{Crc32c, crc32c};
+use crate::tree::{CityTree128, CityTree256};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// The function [`hash_file`] applies to the file contents.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// [`city_hash32`](crate::city_hash32).
+    City32,
+    /// [`city_hash64`](crate::city_hash64).
+    City64,
+    /// [`city_hash128`](crate::city_hash128).
+    City128,
+    /// [`city_hash128_crc`](crate::city_hash128_crc).
+    City128Crc,
+    /// [`city_hash256_crc`](crate::city_hash256_crc).
+    City256Crc,
+    /// [`CityTree128::hash`](crate::CityTree128::hash).
+    Tree128,
+    /// [`CityTree256::hash`](crate::CityTree256::hash).
+    Tree256,
+    /// [`crc32c`](crate::crc32c::crc32c).
+    Crc32c,
+}
+
+/// A digest returned by [`hash_file`], sized by the [`Algorithm`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FileHash {
+    /// From [`Algorithm::City32`] and [`Algorithm::Crc32c`].
+    U32(u32),
+    /// From [`Algorithm::City64`].
+    U64(u64),
+    /// From [`Algorithm::City128`], [`Algorithm::City128Crc`] and
+    /// [`Algorithm::Tree128`].
+    U128(u128),
+    /// From [`Algorithm::City256Crc`] and [`Algorithm::Tree256`].
+    U256(Hash256),
+}
+
+/// The file changed length while it was being hashed.
+///
+/// [`hash_file`] returns it wrapped in an [`io::Error`] of kind
+/// [`io::ErrorKind::InvalidData`]; use [`io::Error::get_ref`] to inspect it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FileChanged {
+    /// Length reported when the file was opened.
+    pub expected: u64,
+    /// Length observed after hashing.
+    pub actual: u64,
+}
+
+impl fmt::Display for FileChanged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "file changed size during hashing: expected {} bytes, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for FileChanged {}
+
+impl Algorithm {
+    /// Applies the algorithm to an in-memory buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The data to hash.
+    ///
+    /// # Returns
+    ///
+    /// The digest, identical to calling the one-shot function directly.
+    pub fn hash(self, bytes: &[u8]) -> FileHash {
+        match self {
+            Algorithm::City32 => FileHash::U32(city_hash32(bytes)),
+            Algorithm::City64 => FileHash::U64(city_hash64(bytes)),
+            Algorithm::City128 => FileHash::U128(city_hash128(bytes)),
+            Algorithm::City128Crc => FileHash::U128(city_hash128_crc(bytes)),
+            Algorithm::City256Crc => FileHash::U256(city_hash256_crc(bytes)),
+            Algorithm::Tree128 => FileHash::U128(CityTree128::hash(bytes)),
+            Algorithm::Tree256 => FileHash::U256(CityTree256::hash(bytes)),
+            Algorithm::Crc32c => FileHash::U32(crc32c(bytes)),
+        }
+    }
+}
+
+fn changed(expected: u64, actual: u64) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, FileChanged { expected, actual })
+}
+
+/// Reads a reader to its end in fixed chunks, feeding streamable algorithms
+/// chunk by chunk and buffering the rest, since CityHash needs the whole input.
+fn hash_stream(reader: &mut impl Read, algorithm: Algorithm) -> io::Result<(FileHash, u64)> {
+    let mut buf = vec![0u8; 1 << 16];
+    let mut total = 0u64;
+    let mut feed = |f: &mut dyn FnMut(&[u8])| -> io::Result<u64> {
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => return Ok(total),
+                Ok(n) => {
+                    total += n as u64;
+                    f(&buf[..n]);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    };
+    match algorithm {
+        Algorithm::Tree128 => {
+            let mut hasher = CityTree128::new();
+            let len = feed(&mut |chunk| hasher.update(chunk))?;
+            Ok((FileHash::U128(hasher.finalize()), len))
+        }
+        Algorithm::Tree256 => {
+            let mut hasher = CityTree256::new();
+            let len = feed(&mut |chunk| hasher.update(chunk))?;
+            Ok((FileHash::U256(hasher.finalize()), len))
+        }
+        Algorithm::Crc32c => {
+            let mut hasher = Crc32c::new();
+            let len = feed(&mut |chunk| hasher.update(chunk))?;
+            Ok((FileHash::U32(hasher.finalize()), len))
+        }
+        _ => {
+            let mut data = Vec::new();
+            let len = feed(&mut |chunk| data.extend_from_slice(chunk))?;
+            Ok((algorithm.hash(&data), len))
+        }
+    }
+}
+
+#[cfg(all(feature = "memmap2", not(feature = "portable")))]
+fn hash_regular(file: &File, len: u64, algorithm: Algorithm) -> io::Result<FileHash> {
+    // SAFETY: the mapping is only read. Another process truncating the file
+    // while it is mapped may still fault, as with any file-backed mapping;
+    // size changes that do not are reported below.
+    let map = unsafe { memmap2::MmapOptions::new().len(len as usize).map(file)? };
+    Ok(algorithm.hash(&map))
+}
+
+#[cfg(not(all(feature = "memmap2", not(feature = "portable"))))]
+fn hash_regular(mut file: &File, len: u64, algorithm: Algorithm) -> io::Result<FileHash> {
+    let mut data = Vec::with_capacity(len as usize);
+    file.read_to_end(&mut data)?;
+    if data.len() as u64 != len {
+        return Err(changed(len, data.len() as u64));
+    }
+    Ok(algorithm.hash(&data))
+}
+
+/// Hashes the contents of the file at `path`.
+///
+/// # Arguments
+///
+/// * `path` - The file to hash; regular files, pipes and devices are accepted.
+/// * `algorithm` - The hash to compute.
+///
+/// # Returns
+///
+/// The same digest as reading the file into memory and hashing it with
+/// [`Algorithm::hash`]. An error of kind [`io::ErrorKind::InvalidData`]
+/// carrying [`FileChanged`] is returned when a regular file changes length
+/// while it is hashed.
+///
+/// With the `memmap2` feature regular files are memory-mapped, unless the
+/// `portable` feature forbids the `unsafe` this needs; they are then read
+/// into memory instead.
+pub fn hash_file(path: impl AsRef<Path>, algorithm: Algorithm) -> io::Result<FileHash> {
+    let mut file = File::open(path)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return hash_stream(&mut file, algorithm).map(|(hash, _)| hash);
+    }
+    let len = metadata.len();
+    if len > usize::MAX as u64 {
+        return Err(io::Error::new(
+            io::ErrorKind::OutOfMemory,
+            "file does not fit in the address space",
+        ));
+    }
+    if len == 0 {
+        // Files reported empty may still produce data (for example in procfs),
+        // and an empty mapping is not portable, so stream them instead.
+        return hash_stream(&mut file, algorithm).map(|(hash, _)| hash);
+    }
+    hash_sized(&file, len, algorithm)
+}
+
+/// Hashes a regular file whose length was `len` when it was opened.
+fn hash_sized(file: &File, len: u64, algorithm: Algorithm) -> io::Result<FileHash> {
+    let hash = hash_regular(file, len, algorithm)?;
+    let after = file.metadata()?.len();
+    if after != len {
+        return Err(changed(len, after));
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALGORITHMS: [Algorithm; 8] = [
+        Algorithm::City32,
+        Algorithm::City64,
+        Algorithm::City128,
+        Algorithm::City128Crc,
+        Algorithm::City256Crc,
+        Algorithm::Tree128,
+        Algorithm::Tree256,
+        Algorithm::Crc32c,
+    ];
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("cith-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn test_hash_file_matches_memory() {
+        for len in [0usize, 1, 17, 240, 1000, (1 << 20) + 3] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 31 + len) as u8).collect();
+            let path = temp_path(&format!("file-{len}"));
+            std::fs::write(&path, &data).unwrap();
+            for algorithm in ALGORITHMS {
+                assert_eq!(
+                    hash_file(&path, algorithm).unwrap(),
+                    algorithm.hash(&data),
+                    "{algorithm:?} len {len}"
+                );
+            }
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_hash_stream_matches_memory() {
+        let data: Vec<u8> = (0..300_000u32).map(|i| (i >> 3) as u8).collect();
+        for algorithm in ALGORITHMS {
+            let (hash, len) = hash_stream(&mut &data[..], algorithm).unwrap();
+            assert_eq!(len, data.len() as u64);
+            assert_eq!(hash, algorithm.hash(&data), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn test_hash_file_missing() {
+        let err = hash_file(temp_path("missing"), Algorithm::City64).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_file_changed_error() {
+        let err = changed(10, 12);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let inner = err.get_ref().unwrap().downcast_ref::<FileChanged>();
+        assert_eq!(
+            inner,
+            Some(&FileChanged {
+                expected: 10,
+                actual: 12
+            })
+        );
+    }
+
+    #[test]
+    fn test_file_grew() {
+        let path = temp_path("grew");
+        std::fs::write(&path, [7u8; 100]).unwrap();
+        let file = File::open(&path).unwrap();
+        // As if 10 bytes were appended after the length was read.
+        let err = hash_sized(&file, 90, Algorithm::City64).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let inner = err.get_ref().unwrap().downcast_ref::<FileChanged>();
+        assert_eq!(
+            inner,
+            Some(&FileChanged {
+                expected: 90,
+                actual: 100
+            })
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hash_special_file() {
+        assert_eq!(
+            hash_file("/dev/null", Algorithm::City128).unwrap(),
+            FileHash::U128(city_hash128(&[]))
+        );
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::city_hash128_with_seed;
+use crate::city64::{
+    K0, K1, city_hash64_long, hash64_len_0_to_16, hash64_len_17_to_32, hash64_len_33_to_64,
+    read_le64,
+};
+use crate::murmur::city_murmur_with_seed_impl;
+
+/// Computes the 64-bit CityHash of a fixed-size key.
+///
+/// The length class of the input is selected from `N` at compile time, so for
+/// keys up to 64 bytes the generated code contains no length dispatch at all.
+/// The result is identical to [`crate::city_hash64`] on the same bytes.
+///
+/// # Arguments
+///
+/// * `bytes` - A fixed-size byte array to hash.
+///
+/// # Returns
+///
+/// A `u64` value representing the hash of the input array.
+#[inline]
+pub fn city_hash64_fixed<const N: usize>(bytes: &[u8; N]) -> u64 {
+    if N <= 16 {
+        hash64_len_0_to_16(bytes)
+    } else if N <= 32 {
+        hash64_len_17_to_32(bytes)
+    } else if N <= 64 {
+        hash64_len_33_to_64(bytes)
+    } else {
+        city_hash64_long(bytes)
+    }
+}
+
+/// Computes the 128-bit CityHash of a fixed-size key.
+///
+/// As with [`city_hash64_fixed`], the length class is resolved from `N` at compile
+/// time. The result is identical to [`crate::city_hash128`] on the same bytes.
+///
+/// # Arguments
+///
+/// * `bytes` - A fixed-size byte array to hash.
+///
+/// # Returns
+///
+/// A `u128` value representing the hash of the input array.
+#[inline]
+pub fn city_hash128_fixed<const N: usize>(bytes: &[u8; N]) -> u128 {
+    if N >= 16 {
+        let q0 = read_le64(bytes, 0);
+        let q1 = read_le64(bytes, 8).wrapping_add(K0);
+        let seed = (q0 as u128) | (q1 as u128).wrapping_shl(64);
+        if N - 16 < 128 {
+            city_murmur_with_seed_impl(&bytes[16..], seed)
+        } else {
+            city_hash128_with_seed(&bytes[16..], seed)
+        }
+    } else {
+        city_murmur_with_seed_impl(bytes, K0 as u128 | (K1 as u128).wrapping_shl(64))
+    }
+}
+
+/// Computes the 64-bit CityHash of a `u32` taken as its 4 little-endian bytes.
+///
+/// Equivalent to `city_hash64(&value.to_le_bytes())`.
+#[inline]
+pub fn city_hash64_u32(value: u32) -> u64 {
+    city_hash64_fixed(&value.to_le_bytes())
+}
+
+/// Computes the 64-bit CityHash of a `u64` taken as its 8 little-endian bytes.
+///
+/// Equivalent to `city_hash64(&value.to_le_bytes())`.
+#[inline]
+pub fn city_hash64_u64(value: u64) -> u64 {
+    city_hash64_fixed(&value.to_le_bytes())
+}
+
+/// Computes the 64-bit CityHash of a `u128` taken as its 16 little-endian bytes.
+///
+/// Equivalent to `city_hash64(&value.to_le_bytes())`.
+#[inline]
+pub fn city_hash64_u128(value: u128) -> u64 {
+    city_hash64_fixed(&value.to_le_bytes())
+}
+
+/// Computes the 128-bit CityHash of a `u64` taken as its 8 little-endian bytes.
+///
+/// Equivalent to `city_hash128(&value.to_le_bytes())`.
+#[inline]
+pub fn city_hash128_u64(value: u64) -> u128 {
+    city_hash128_fixed(&value.to_le_bytes())
+}
+
+/// Computes the 128-bit CityHash of a `u128` taken as its 16 little-endian bytes.
+///
+/// Equivalent to `city_hash128(&value.to_le_bytes())`.
+#[inline]
+pub fn city_hash128_u128(value: u128) -> u128 {
+    city_hash128_fixed(&value.to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{city_hash64, city_hash128};
+
+    macro_rules! check_fixed {
+        ($($n:literal),*) => {
+            $(
+                let data: [u8; $n] = std::array::from_fn(|i| (i as u8).wrapping_mul(31).wrapping_add(7));
+                assert_eq!(city_hash64_fixed(&data), city_hash64(&data), "city_hash64 len {}", $n);
+                assert_eq!(city_hash128_fixed(&data), city_hash128(&data), "city_hash128 len {}", $n);
+            )*
+        };
+    }
+
+    #[test]
+    fn test_fixed_matches_slices() {
+        check_fixed!(
+            0, 1, 2, 3, 4, 5, 7, 8, 9, 12, 15, 16, 17, 20, 24, 31, 32, 33, 40, 48, 63, 64, 65, 96,
+            127, 128, 129, 143, 144, 145, 160, 200, 256, 300, 1000
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        let values = [0u64, 1, 0xdead_beef, u64::MAX, 0x0123_4567_89ab_cdef];
+        for &v in values.iter() {
+            assert_eq!(
+                city_hash64_u32(v as u32),
+                city_hash64(&(v as u32).to_le_bytes())
+            );
+            assert_eq!(city_hash64_u64(v), city_hash64(&v.to_le_bytes()));
+            let wide = (v as u128) | ((!v as u128) << 64);
+            assert_eq!(city_hash64_u128(wide), city_hash64(&wide.to_le_bytes()));
+            assert_eq!(city_hash128_u64(v), city_hash128(&v.to_le_bytes()));
+            assert_eq!(city_hash128_u128(wide), city_hash128(&wide.to_le_bytes()));
+        }
+    }
+}
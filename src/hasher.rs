@@ -30,6 +30,33 @@ use crate::city32::city_hash32;
 use crate::{city_hash64, city_hash64_with_seed};
 use std::hash::Hasher;
 
+/// Overrides the integer writes of [`Hasher`], which default to native byte
+/// order, to write little-endian bytes like the rest of the crate.
+macro_rules! write_le {
+    ($($method:ident($ty:ty)),* $(,)?) => {$(
+        fn $method(&mut self, i: $ty) {
+            self.write(&i.to_le_bytes());
+        }
+    )*};
+}
+
+macro_rules! le_integer_writes {
+    () => {
+        write_le!(
+            write_u16(u16),
+            write_u32(u32),
+            write_u64(u64),
+            write_u128(u128),
+            write_usize(usize),
+            write_i16(i16),
+            write_i32(i32),
+            write_i64(i64),
+            write_i128(i128),
+            write_isize(isize),
+        );
+    };
+}
+
 /// A [`Hasher`] implementation using the 32-bit variant of CityHash.
 ///
 /// `City32Hasher` computes a fast, non-cryptographic 32-bit hash of input data
@@ -38,6 +65,9 @@ use std::hash::Hasher;
 ///
 /// This hasher can be used with collections such as [`HashMap`] or [`HashSet`]
 /// where a lightweight and deterministic hash function is desired.
+///
+/// Integers are written little-endian, so they hash the same on every host;
+/// `usize` and `isize` still take the width of the host's pointers.
 pub struct City32Hasher {
     bytes: Vec<u8>,
 }
@@ -51,6 +81,8 @@ impl Hasher for City32Hasher {
         let mut copy = bytes.to_vec();
         self.bytes.append(&mut copy);
     }
+
+    le_integer_writes!();
 }
 
 impl Default for City32Hasher {
@@ -68,6 +100,9 @@ impl Default for City32Hasher {
 /// This hasher can be used with collections like [`HashMap`] or [`HashSet`] when
 /// deterministic, fast hashing is desired (e.g., for data indexing, caching, or
 /// fingerprinting).
+///
+/// Integers are written little-endian, so they hash the same on every host;
+/// `usize` and `isize` still take the width of the host's pointers.
 pub struct City64Hasher {
     bytes: Vec<u8>,
     seed: u64,
@@ -86,6 +121,8 @@ impl Hasher for City64Hasher {
         let mut copy = bytes.to_vec();
         self.bytes.append(&mut copy);
     }
+
+    le_integer_writes!();
 }
 
 impl Default for City64Hasher {
@@ -119,3 +156,36 @@ impl City64Hasher {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::{BuildHasher, BuildHasherDefault};
+
+    #[test]
+    fn test_integers_little_endian() {
+        let state = BuildHasherDefault::<City64Hasher>::default();
+        assert_eq!(
+            state.hash_one(42u64),
+            city_hash64(&[42, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(state.hash_one(0x0102u16), city_hash64(&[2, 1]));
+        assert_eq!(
+            state.hash_one(-2i32),
+            city_hash64(&[0xfe, 0xff, 0xff, 0xff])
+        );
+        let mut wide = [0u8; 16];
+        wide[15] = 0x80;
+        assert_eq!(state.hash_one(1u128 << 127), city_hash64(&wide));
+        assert_eq!(state.hash_one("ab"), city_hash64(&[b'a', b'b', 0xff]));
+
+        let state = BuildHasherDefault::<City32Hasher>::default();
+        assert_eq!(
+            state.hash_one(0x0102_0304u32),
+            city_hash32(&[4, 3, 2, 1]) as u64
+        );
+        let mut hasher = City64Hasher::new_with_seed(7);
+        hasher.write_i64(-1);
+        assert_eq!(hasher.finish(), city_hash64_with_seed(&[0xff; 8], 7));
+    }
+}
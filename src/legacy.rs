@@ -0,0 +1,209 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Pieces shared by the CityHash v1.0.2 and v1.0.3 releases.
+use crate::city32::read_le32;
+use crate::city64::{K0, K1, K2, hash_len_16_u64, read_le64, shift_mix};
+use crate::murmur::city_murmur_core;
+
+pub(crate) const K3: u64 = 0xc949d7c7509e6557;
+
+#[inline]
+fn hash_len_0_to_16(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    if len > 8 {
+        let a = read_le64(bytes, 0);
+        let b = read_le64(bytes, len - 8);
+        hash_len_16_u64(a, b.wrapping_add(len as u64).rotate_right(len as u32)) ^ b
+    } else if len >= 4 {
+        let a = read_le32(bytes, 0) as u64;
+        hash_len_16_u64(
+            (len as u64).wrapping_add(a << 3),
+            read_le32(bytes, len - 4) as u64,
+        )
+    } else if len > 0 {
+        let a = bytes[0] as u32;
+        let b = bytes[len >> 1] as u32;
+        let c = bytes[len - 1] as u32;
+        let y = a.wrapping_add(b << 8);
+        let z = (len as u32).wrapping_add(c << 2);
+        shift_mix((y as u64).wrapping_mul(K2) ^ (z as u64).wrapping_mul(K3)).wrapping_mul(K2)
+    } else {
+        K2
+    }
+}
+
+#[inline]
+fn hash_len_17_to_32(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    let a = read_le64(bytes, 0).wrapping_mul(K1);
+    let b = read_le64(bytes, 8);
+    let c = read_le64(bytes, len - 8).wrapping_mul(K2);
+    let d = read_le64(bytes, len - 16).wrapping_mul(K0);
+    hash_len_16_u64(
+        a.wrapping_sub(b)
+            .rotate_right(43)
+            .wrapping_add(c.rotate_right(30))
+            .wrapping_add(d),
+        a.wrapping_add((b ^ K3).rotate_right(20))
+            .wrapping_sub(c)
+            .wrapping_add(len as u64),
+    )
+}
+
+#[inline]
+fn hash_len_33_to_64(bytes: &[u8]) -> u64 {
+    let len = bytes.len();
+    let mut z = read_le64(bytes, 24);
+    let mut a = read_le64(bytes, 0).wrapping_add(
+        (len as u64)
+            .wrapping_add(read_le64(bytes, len - 16))
+            .wrapping_mul(K0),
+    );
+    let mut b = a.wrapping_add(z).rotate_right(52);
+    let mut c = a.rotate_right(37);
+    a = a.wrapping_add(read_le64(bytes, 8));
+    c = c.wrapping_add(a.rotate_right(7));
+    a = a.wrapping_add(read_le64(bytes, 16));
+    let vf = a.wrapping_add(z);
+    let vs = b.wrapping_add(a.rotate_right(31)).wrapping_add(c);
+    a = read_le64(bytes, 16).wrapping_add(read_le64(bytes, len - 32));
+    z = read_le64(bytes, len - 8);
+    b = a.wrapping_add(z).rotate_right(52);
+    c = a.rotate_right(37);
+    a = a.wrapping_add(read_le64(bytes, len - 24));
+    c = c.wrapping_add(a.rotate_right(7));
+    a = a.wrapping_add(read_le64(bytes, len - 16));
+    let wf = a.wrapping_add(z);
+    let ws = b.wrapping_add(a.rotate_right(31)).wrapping_add(c);
+    let r = shift_mix(
+        vf.wrapping_add(ws)
+            .wrapping_mul(K2)
+            .wrapping_add(wf.wrapping_add(vs).wrapping_mul(K0)),
+    );
+    shift_mix(r.wrapping_mul(K0).wrapping_add(vs)).wrapping_mul(K2)
+}
+
+/// CityHash64 of the legacy releases; only the path for inputs over 64 bytes
+/// differs between them and is passed in as `hash_long`.
+#[inline]
+pub(crate) fn legacy_hash64(bytes: &[u8], hash_long: fn(&[u8]) -> u64) -> u64 {
+    let len = bytes.len();
+    if len <= 16 {
+        hash_len_0_to_16(bytes)
+    } else if len <= 32 {
+        hash_len_17_to_32(bytes)
+    } else if len <= 64 {
+        hash_len_33_to_64(bytes)
+    } else {
+        hash_long(bytes)
+    }
+}
+
+/// CityMurmur as used by the legacy CityHash128 for inputs under 128 bytes.
+#[inline]
+pub(crate) fn legacy_murmur(bytes: &[u8], seed: u128) -> u128 {
+    city_murmur_core(bytes, seed, hash_len_0_to_16)
+}
+
+/// Legacy CityHash128, which derives the seed from the first bytes and hands
+/// the rest to the release's `with_seed` function.
+#[inline]
+pub(crate) fn legacy_hash128(bytes: &[u8], with_seed: fn(&[u8], u128) -> u128) -> u128 {
+    let len = bytes.len();
+    let seed = |lo: u64, hi: u64| (lo as u128) | ((hi as u128) << 64);
+    if len >= 16 {
+        with_seed(
+            &bytes[16..],
+            seed(read_le64(bytes, 0) ^ K3, read_le64(bytes, 8)),
+        )
+    } else if len >= 8 {
+        with_seed(
+            &[],
+            seed(
+                read_le64(bytes, 0) ^ (len as u64).wrapping_mul(K0),
+                read_le64(bytes, len - 8) ^ K1,
+            ),
+        )
+    } else {
+        with_seed(bytes, seed(K0, K1))
+    }
+}
+
+/// Parses a historical `city-test.cc` table stored as whitespace separated hex
+/// words, seven per row: CityHash64, CityHash64WithSeed, CityHash64WithSeeds and
+/// the low and high halves of CityHash128 and CityHash128WithSeed.
+#[cfg(test)]
+pub(crate) fn parse_table(text: &str) -> Vec<[u64; 7]> {
+    text.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut row = [0u64; 7];
+            let mut words = line.split_whitespace();
+            for word in row.iter_mut() {
+                *word = u64::from_str_radix(words.next().unwrap(), 16).unwrap();
+            }
+            row
+        })
+        .collect()
+}
+
+/// Test data of the v1.0.x `city-test.cc`: 1 MiB of pseudo-random bytes.
+///
+/// Under Miri, only the bytes of the rows up to 298 bytes, which are the only
+/// rows [`legacy_test_rows`] returns there.
+#[cfg(test)]
+pub(crate) fn legacy_test_data() -> Vec<u8> {
+    let len = if cfg!(miri) { 298 * 298 + 298 } else { 1 << 20 };
+    let mut a = 9u64;
+    let mut b = 777u64;
+    (0..len)
+        .map(|i| {
+            a = (a ^ (a >> 41)).wrapping_mul(K0).wrapping_add(b);
+            b = (b ^ (b >> 41)).wrapping_mul(K0).wrapping_add(i as u64);
+            (b >> 37) as u8
+        })
+        .collect()
+}
+
+/// Number of rows of a 300-row `city-test.cc` table to check; Miri skips the
+/// last row, which hashes the whole 1 MiB.
+#[cfg(test)]
+pub(crate) fn legacy_test_rows() -> usize {
+    if cfg!(miri) { 299 } else { 300 }
+}
+
+/// Input of row `i` of the `city-test.cc` table; the last row is the whole buffer.
+#[cfg(test)]
+pub(crate) fn test_input(data: &[u8], i: usize, rows: usize) -> &[u8] {
+    if i == rows - 1 {
+        data
+    } else {
+        &data[i * i..i * i + i]
+    }
+}
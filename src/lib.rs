@@ -26,20 +26,52 @@
  * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
  * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
  */
+#![cfg_attr(feature = "portable", forbid(unsafe_code))]
 
+pub mod adaptive;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+mod backend;
 mod city128;
 mod city256;
 mod city32;
 mod city64;
+#[cfg(test)]
+mod city_test;
+pub mod clickhouse;
 mod crc;
+pub mod crc32c;
+#[cfg(feature = "digest")]
+pub mod digest;
+pub mod farm;
+pub mod file;
+mod fixed;
 mod hasher;
+mod legacy;
 mod murmur;
+#[cfg(test)]
+mod proptests;
+mod tree;
+pub mod v1_0_2;
+pub mod v1_0_3;
 
+pub use backend::{
+    BACKEND_ENV_VAR, Backend, BackendUnavailable, ParseBackendError, backend, force_backend,
+};
 pub use city32::city_hash32;
-pub use city64::{city_hash64, city_hash64_with_seed};
+pub use city64::{
+    city_hash64, city_hash64_with_seed, city_hash64_with_seeds, city_hash64_with_seeds_many,
+    hash128_to_64,
+};
 pub use city128::{
-    city_hash128, city_hash128_crc, city_hash128_crc_with_seed, city_hash128_with_seed,
+    city_hash128, city_hash128_crc, city_hash128_crc_with_seed, city_hash128_crc_with_seeds_many,
+    city_hash128_with_seed,
+};
+pub use city256::{Hash256, city_hash_crc256_words, city_hash256_crc, city_hash256_crc_with_seed};
+pub use fixed::{
+    city_hash64_fixed, city_hash64_u32, city_hash64_u64, city_hash64_u128, city_hash128_fixed,
+    city_hash128_u64, city_hash128_u128,
 };
-pub use city256::{Hash256, city_hash256_crc, city_hash256_crc_with_seed};
 pub use hasher::{City32Hasher, City64Hasher};
 pub use murmur::{city_murmur, city_murmur_with_seed};
+pub use tree::{CityTree128, CityTree256, TREE_LEAF_SIZE, TREE_VERSION};
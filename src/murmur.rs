@@ -40,7 +40,14 @@ fn hash_len16(lo: u64, hi: u64) -> u64 {
 }
 
 #[inline]
-fn city_murmur_with_seed_impl(bytes: &[u8], seed: u128) -> u128 {
+pub(crate) fn city_murmur_with_seed_impl(bytes: &[u8], seed: u128) -> u128 {
+    city_murmur_core(bytes, seed, hash64_len_0_to_16)
+}
+
+/// CityMurmur body shared with the legacy releases, which differ only in the
+/// short-input hash `hash_short` mixed in for inputs of up to 16 bytes.
+#[inline(always)]
+pub(crate) fn city_murmur_core(bytes: &[u8], seed: u128, hash_short: fn(&[u8]) -> u64) -> u128 {
     let mut a = (seed & 0xffff_ffff_ffff_ffff) as u64;
     let mut b = (seed >> 64) as u64;
     let mut c;
@@ -48,7 +55,7 @@ fn city_murmur_with_seed_impl(bytes: &[u8], seed: u128) -> u128 {
     let len = bytes.len();
     if len <= 16 {
         a = shift_mix(a.wrapping_mul(K1)).wrapping_mul(K1);
-        c = b.wrapping_mul(K1).wrapping_add(hash64_len_0_to_16(bytes));
+        c = b.wrapping_mul(K1).wrapping_add(hash_short(bytes));
         d = shift_mix(a.wrapping_add(if len >= 8 { read_le64(bytes, 0) } else { c }));
     } else {
         c = hash_len16(read_le64(bytes, len - 8).wrapping_add(K1), a);
@@ -56,7 +63,7 @@ fn city_murmur_with_seed_impl(bytes: &[u8], seed: u128) -> u128 {
             b.wrapping_add(len as u64),
             c.wrapping_add(read_le64(bytes, len - 16)),
         );
-        a += d;
+        a = a.wrapping_add(d);
         let iters = (bytes.len() - 1) / 16;
         let sliced = &bytes[..iters * 16];
         for chunk in sliced.chunks_exact(32) {
@@ -130,7 +137,7 @@ pub fn city_murmur(bytes: &[u8]) -> u128 {
 
 #[cfg(test)]
 mod tests {
-    use crate::murmur::city_murmur;
+    use crate::murmur::{city_murmur, city_murmur_with_seed};
 
     #[test]
     fn test_city_murmur() {
@@ -154,4 +161,19 @@ mod tests {
             49367195754802758346567295079188952398
         );
     }
+
+    #[test]
+    fn test_city_murmur_seed_overflow() {
+        // `a + d` wrapped for a low seed word near `u64::MAX` and overflowed
+        // in debug builds. Values are from the reference CityHash128WithSeed.
+        let input = b"The quick brown fox jumps over the lazy dog";
+        assert_eq!(
+            city_murmur_with_seed(input, u128::MAX),
+            327619942213756287151034112202603065523
+        );
+        assert_eq!(
+            city_murmur_with_seed(input, u64::MAX as u128),
+            23358751695939475165074013148298169601
+        );
+    }
 }
@@ -0,0 +1,429 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! Property tests over the whole public API.
+//!
+//! Every function is called on every length from 0 to [`MAX_LEN`], on each
+//! available [`Backend`], and on random inputs and seeds, so an off-by-one in
+//! the offset arithmetic of a length class shows up as a panic here. Every
+//! incremental hasher, including the `digest` types, implements
+//! [`Incremental`] and must give the one-shot result for any split of the
+//! input into writes.
+use crate::adaptive::{AdaptiveHasher, AdaptiveState};
+use crate::backend::Backend;
+use crate::city128::city128_crc_with;
+use crate::city256::city256_crc_with;
+use crate::clickhouse::Value;
+use crate::clickhouse::block::{BlockReader, Method, encode_block};
+use crate::crc32c::{Crc32c, crc32c, crc32c_append, crc32c_combine, crc32c_update_with};
+use crate::file::Algorithm;
+use crate::*;
+use proptest::prelude::*;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hasher};
+
+/// Longest input of the sweeps.
+const MAX_LEN: usize = 2048;
+
+/// Calls the fixed-length functions on the prefixes of `bytes` of length `N`.
+macro_rules! hash_fixed {
+    ($bytes:expr, $($n:literal)*) => {$(
+        if let Some(prefix) = $bytes.first_chunk::<$n>() {
+            city_hash64_fixed(prefix);
+            city_hash128_fixed(prefix);
+        }
+    )*};
+}
+
+/// Calls every public function that hashes a byte slice.
+fn hash_all(bytes: &[u8], seed: u64, seed1: u64, seed128: u128) {
+    city_hash32(bytes);
+    city_hash64(bytes);
+    city_hash64_with_seed(bytes, seed);
+    city_hash64_with_seeds(bytes, seed, seed1);
+    city_hash64_with_seeds_many(bytes, &[seed, seed1], &mut [0; 2]);
+    city_hash128(bytes);
+    city_hash128_with_seed(bytes, seed128);
+    city_hash128_crc(bytes);
+    city_hash128_crc_with_seed(bytes, seed128);
+    city_hash128_crc_with_seeds_many(bytes, &[seed128, 0], &mut [0; 2]);
+    city_hash256_crc(bytes);
+    city_hash256_crc_with_seed(bytes, seed as u32);
+    city_hash_crc256_words(bytes);
+    city_murmur(bytes);
+    city_murmur_with_seed(bytes, seed128);
+    hash_fixed!(bytes,
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
+        33 48 63 64 65 96 127 128 129 200 239 240 241 255 256 512);
+
+    crc32c(bytes);
+    crc32c_append(seed as u32, bytes);
+    crc32c_combine(seed as u32, seed1 as u32, bytes.len() as u64);
+    CityTree128::hash(bytes);
+    CityTree256::hash(bytes);
+    for algorithm in [
+        Algorithm::City32,
+        Algorithm::City64,
+        Algorithm::City128,
+        Algorithm::City128Crc,
+        Algorithm::City256Crc,
+        Algorithm::Tree128,
+        Algorithm::Tree256,
+        Algorithm::Crc32c,
+    ] {
+        algorithm.hash(bytes);
+    }
+
+    v1_0_2::city_hash64(bytes);
+    v1_0_2::city_hash64_with_seed(bytes, seed);
+    v1_0_2::city_hash128(bytes);
+    v1_0_2::city_hash128_with_seed(bytes, seed128);
+    v1_0_3::city_hash64(bytes);
+    v1_0_3::city_hash64_with_seed(bytes, seed);
+    v1_0_3::city_hash128(bytes);
+    v1_0_3::city_hash128_with_seed(bytes, seed128);
+
+    farm::hash32(bytes);
+    farm::hash32_with_seed(bytes, seed as u32);
+    farm::hash64(bytes);
+    farm::hash64_with_seed(bytes, seed);
+    farm::hash64_with_seeds(bytes, seed, seed1);
+    farm::hash128(bytes);
+    farm::hash128_with_seed(bytes, seed128);
+    farm::fingerprint32(bytes);
+    farm::fingerprint64(bytes);
+    farm::fingerprint128(bytes);
+    farm::na::hash64(bytes);
+    farm::na::hash64_with_seed(bytes, seed);
+    farm::na::hash64_with_seeds(bytes, seed, seed1);
+    farm::uo::hash64(bytes);
+    farm::uo::hash64_with_seed(bytes, seed);
+    farm::uo::hash64_with_seeds(bytes, seed, seed1);
+    farm::xo::hash64(bytes);
+    farm::xo::hash64_with_seed(bytes, seed);
+    farm::xo::hash64_with_seeds(bytes, seed, seed1);
+    farm::mk::hash32(bytes);
+    farm::mk::hash32_with_seed(bytes, seed as u32);
+    farm::cc::hash32(bytes);
+    farm::cc::hash32_with_seed(bytes, seed as u32);
+    farm::cc::fingerprint128(bytes);
+    farm::cc::city_hash128_with_seed(bytes, seed128);
+
+    let values = [Value::String(bytes), Value::UInt64(seed)];
+    clickhouse::city_hash64(&[
+        Value::FixedString(bytes),
+        Value::Array(&values),
+        Value::Tuple(&values),
+    ]);
+    // Arbitrary bytes must decode to blocks or an error, never a panic.
+    let mut reader = BlockReader::new(bytes);
+    while let Ok(Some(_)) = reader.read_block() {}
+}
+
+/// Calls the CRC functions on one backend.
+fn hash_crc_with(backend: Backend, bytes: &[u8], seed: u64, seed128: u128) {
+    city128_crc_with(backend, bytes, None);
+    city128_crc_with(backend, bytes, Some(seed128));
+    city256_crc_with(backend, bytes, seed as u32);
+    crc32c_update_with(backend, seed as u32, bytes);
+}
+
+/// Deterministic pseudo-random bytes.
+fn data(len: usize) -> Vec<u8> {
+    (0..len)
+        .map(|i| {
+            (i as u64)
+                .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+                .rotate_left(29) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_length() {
+    let data = data(MAX_LEN);
+    for len in 0..=MAX_LEN {
+        let bytes = &data[..len];
+        hash_all(
+            bytes,
+            len as u64,
+            !(len as u64),
+            u128::MAX / (len as u128 + 1),
+        );
+        for backend in Backend::available() {
+            hash_crc_with(backend, bytes, len as u64, len as u128);
+        }
+    }
+}
+
+/// An incremental hasher and the one-shot function it must agree with.
+trait Incremental {
+    type Output: PartialEq + Debug;
+
+    fn new(seed: u64) -> Self;
+
+    fn write(&mut self, bytes: &[u8]);
+
+    fn finish(&self) -> Self::Output;
+
+    fn one_shot(bytes: &[u8], seed: u64) -> Self::Output;
+}
+
+impl Incremental for City32Hasher {
+    type Output = u64;
+
+    fn new(_: u64) -> Self {
+        City32Hasher::default()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        Hasher::write(self, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        Hasher::finish(self)
+    }
+
+    fn one_shot(bytes: &[u8], _: u64) -> u64 {
+        city_hash32(bytes) as u64
+    }
+}
+
+impl Incremental for City64Hasher {
+    type Output = u64;
+
+    fn new(seed: u64) -> Self {
+        City64Hasher::new_with_seed(seed)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        Hasher::write(self, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        Hasher::finish(self)
+    }
+
+    fn one_shot(bytes: &[u8], seed: u64) -> u64 {
+        // A zero seed stands for the unseeded CityHash64.
+        if seed == 0 {
+            city_hash64(bytes)
+        } else {
+            city_hash64_with_seed(bytes, seed)
+        }
+    }
+}
+
+impl Incremental for AdaptiveHasher {
+    type Output = u64;
+
+    fn new(seed: u64) -> Self {
+        AdaptiveState::with_seed(seed).build_hasher()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        Hasher::write(self, bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        Hasher::finish(self)
+    }
+
+    fn one_shot(bytes: &[u8], seed: u64) -> u64 {
+        city_hash64_with_seed(bytes, seed)
+    }
+}
+
+impl Incremental for Crc32c {
+    type Output = u32;
+
+    fn new(seed: u64) -> Self {
+        Crc32c::new_with_initial(seed as u32)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+
+    fn finish(&self) -> u32 {
+        self.finalize()
+    }
+
+    fn one_shot(bytes: &[u8], seed: u64) -> u32 {
+        crc32c_append(seed as u32, bytes)
+    }
+}
+
+/// Implements [`Incremental`] for the unseeded tree hashers.
+macro_rules! incremental_tree {
+    ($($name:ident => $node:ty),*) => {$(
+        impl Incremental for $name {
+            type Output = $node;
+
+            fn new(_: u64) -> Self {
+                $name::new()
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.update(bytes);
+            }
+
+            fn finish(&self) -> $node {
+                self.finalize()
+            }
+
+            fn one_shot(bytes: &[u8], _: u64) -> $node {
+                $name::hash(bytes)
+            }
+        }
+    )*};
+}
+
+incremental_tree!(CityTree128 => u128, CityTree256 => Hash256);
+
+/// Implements [`Incremental`] for the `digest` types, whose output is the
+/// little-endian words of `$hash(bytes, $seed(seed))`.
+#[cfg(feature = "digest")]
+macro_rules! incremental_digest {
+    ($($name:ident: |$seed:ident| $seeded:expr => |$bytes:ident, $s:ident| $hash:expr),* $(,)?) => {$(
+        impl Incremental for crate::digest::$name {
+            type Output = Vec<u8>;
+
+            fn new($seed: u64) -> Self {
+                crate::digest::$name::new_with_seed($seeded)
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                ::digest::Update::update(self, bytes);
+            }
+
+            fn finish(&self) -> Vec<u8> {
+                ::digest::FixedOutput::finalize_fixed(self.clone()).to_vec()
+            }
+
+            fn one_shot($bytes: &[u8], $seed: u64) -> Vec<u8> {
+                let $s = $seeded;
+                let words: Vec<u64> = $hash;
+                words.iter().flat_map(|word| word.to_le_bytes()).collect()
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "digest")]
+fn halves(x: u128) -> Vec<u64> {
+    vec![x as u64, (x >> 64) as u64]
+}
+
+#[cfg(feature = "digest")]
+incremental_digest!(
+    City64: |seed| seed => |bytes, s| vec![city_hash64_with_seed(bytes, s)],
+    City128: |seed| (seed as u128) << 64 | seed as u128
+        => |bytes, s| halves(city_hash128_with_seed(bytes, s)),
+    City256Crc: |seed| seed as u32 => |bytes, s| {
+        let hash = city_hash256_crc_with_seed(bytes, s);
+        [halves(hash.lo), halves(hash.hi)].concat()
+    },
+    CityMurmur: |seed| (seed as u128) << 64 | seed as u128
+        => |bytes, s| halves(city_murmur_with_seed(bytes, s)),
+);
+
+/// Writes `bytes` to `H` in pieces ending at the `cuts`, which index into
+/// the input modulo its length plus one.
+fn split_finish<H: Incremental>(bytes: &[u8], seed: u64, cuts: &[usize]) -> H::Output {
+    let mut cuts: Vec<usize> = cuts.iter().map(|cut| cut % (bytes.len() + 1)).collect();
+    cuts.sort_unstable();
+    let mut hasher = H::new(seed);
+    let mut start = 0;
+    for cut in cuts.into_iter().chain([bytes.len()]) {
+        hasher.write(&bytes[start..cut]);
+        start = cut;
+    }
+    hasher.finish()
+}
+
+/// Generates a split-equivalence property per incremental hasher.
+macro_rules! split_equivalence {
+    ($($test:ident: $hasher:ty),* $(,)?) => {
+        proptest! {
+            $(
+                #[test]
+                fn $test(
+                    bytes in prop::collection::vec(any::<u8>(), 0..=MAX_LEN),
+                    seed in any::<u64>(),
+                    cuts in prop::collection::vec(any::<usize>(), 0..8),
+                ) {
+                    prop_assert_eq!(
+                        split_finish::<$hasher>(&bytes, seed, &cuts),
+                        <$hasher>::one_shot(&bytes, seed)
+                    );
+                }
+            )*
+        }
+    };
+}
+
+split_equivalence!(
+    prop_split_city32: City32Hasher,
+    prop_split_city64: City64Hasher,
+    prop_split_adaptive: AdaptiveHasher,
+    prop_split_crc32c: Crc32c,
+    prop_split_tree128: CityTree128,
+    prop_split_tree256: CityTree256,
+);
+
+#[cfg(feature = "digest")]
+split_equivalence!(
+    prop_split_digest_city64: crate::digest::City64,
+    prop_split_digest_city128: crate::digest::City128,
+    prop_split_digest_city256_crc: crate::digest::City256Crc,
+    prop_split_digest_city_murmur: crate::digest::CityMurmur,
+);
+
+proptest! {
+    #[test]
+    fn prop_no_panic(
+        bytes in prop::collection::vec(any::<u8>(), 0..=MAX_LEN),
+        seed in any::<u64>(),
+        seed1 in any::<u64>(),
+        seed128 in any::<u128>(),
+    ) {
+        hash_all(&bytes, seed, seed1, seed128);
+        for backend in Backend::available() {
+            hash_crc_with(backend, &bytes, seed, seed128);
+        }
+    }
+
+    #[test]
+    fn prop_blocks_round_trip(bytes in prop::collection::vec(any::<u8>(), 0..=MAX_LEN)) {
+        let block = encode_block(Method::None, &bytes);
+        let mut reader = BlockReader::new(block.as_slice());
+        prop_assert_eq!(reader.read_block().unwrap(), Some(bytes));
+        prop_assert_eq!(reader.read_block().unwrap(), None);
+    }
+}
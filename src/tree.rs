@@ -0,0 +1,399 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+use crate::city128::city_hash128;
+use crate::city256::{Hash256, city_hash256_crc};
+
+/// Size in bytes of every tree leaf except possibly the last one.
+pub const TREE_LEAF_SIZE: usize = 1 << 20;
+
+/// Version of the tree layout, mixed into every root.
+pub const TREE_VERSION: u8 = 1;
+
+const PARENT_TAG: u8 = 0x01;
+const ROOT_TAG: u8 = 0x02;
+
+/// Node hash of one tree flavour.
+trait TreeNode: Copy + Send {
+    const SIZE: usize;
+
+    fn leaf(bytes: &[u8]) -> Self;
+
+    fn to_le_bytes(self, dst: &mut [u8]);
+
+    fn hash(bytes: &[u8]) -> Self;
+
+    fn parent(left: Self, right: Self) -> Self {
+        let mut buf = [0u8; 65];
+        buf[0] = PARENT_TAG;
+        left.to_le_bytes(&mut buf[1..1 + Self::SIZE]);
+        right.to_le_bytes(&mut buf[1 + Self::SIZE..1 + 2 * Self::SIZE]);
+        Self::hash(&buf[..1 + 2 * Self::SIZE])
+    }
+
+    fn root(node: Self, total_len: u64) -> Self {
+        let mut buf = [0u8; 42];
+        buf[0] = ROOT_TAG;
+        buf[1] = TREE_VERSION;
+        node.to_le_bytes(&mut buf[2..2 + Self::SIZE]);
+        buf[2 + Self::SIZE..10 + Self::SIZE].copy_from_slice(&total_len.to_le_bytes());
+        Self::hash(&buf[..10 + Self::SIZE])
+    }
+}
+
+impl TreeNode for u128 {
+    const SIZE: usize = 16;
+
+    fn leaf(bytes: &[u8]) -> Self {
+        city_hash128(bytes)
+    }
+
+    fn to_le_bytes(self, dst: &mut [u8]) {
+        dst.copy_from_slice(&u128::to_le_bytes(self));
+    }
+
+    fn hash(bytes: &[u8]) -> Self {
+        city_hash128(bytes)
+    }
+}
+
+impl TreeNode for Hash256 {
+    const SIZE: usize = 32;
+
+    fn leaf(bytes: &[u8]) -> Self {
+        city_hash256_crc(bytes)
+    }
+
+    fn to_le_bytes(self, dst: &mut [u8]) {
+        dst[..16].copy_from_slice(&self.lo.to_le_bytes());
+        dst[16..].copy_from_slice(&self.hi.to_le_bytes());
+    }
+
+    fn hash(bytes: &[u8]) -> Self {
+        city_hash256_crc(bytes)
+    }
+}
+
+/// Hashes the subtree over `bytes`, splitting off the largest power-of-two
+/// number of leaves strictly below the leaf count as the left child.
+fn subtree<N: TreeNode>(bytes: &[u8]) -> N {
+    let leaves = bytes.len().div_ceil(TREE_LEAF_SIZE);
+    if leaves <= 1 {
+        return N::leaf(bytes);
+    }
+    let split = 1usize << (usize::BITS - 1 - (leaves - 1).leading_zeros());
+    let (left, right) = bytes.split_at(split * TREE_LEAF_SIZE);
+    #[cfg(feature = "rayon")]
+    let (left, right) = rayon::join(|| subtree::<N>(left), || subtree::<N>(right));
+    #[cfg(not(feature = "rayon"))]
+    let (left, right) = (subtree::<N>(left), subtree::<N>(right));
+    N::parent(left, right)
+}
+
+fn tree_hash<N: TreeNode>(bytes: &[u8]) -> N {
+    N::root(subtree(bytes), bytes.len() as u64)
+}
+
+/// Streaming state shared by both tree flavours. Complete subtrees are kept on
+/// a stack and merged as soon as two of them cover the same number of leaves.
+#[derive(Clone, Debug)]
+struct TreeState<N> {
+    stack: Vec<(N, u64)>,
+    buffer: Vec<u8>,
+    total_len: u64,
+}
+
+impl<N: TreeNode> TreeState<N> {
+    fn new() -> Self {
+        TreeState {
+            stack: Vec::new(),
+            buffer: Vec::new(),
+            total_len: 0,
+        }
+    }
+
+    fn push_leaf(&mut self, node: N) {
+        let mut node = (node, 1u64);
+        while let Some(&(left, count)) = self.stack.last() {
+            if count != node.1 {
+                break;
+            }
+            self.stack.pop();
+            node = (N::parent(left, node.0), count * 2);
+        }
+        self.stack.push(node);
+    }
+
+    fn update(&mut self, mut bytes: &[u8]) {
+        self.total_len += bytes.len() as u64;
+        if !self.buffer.is_empty() {
+            let take = (TREE_LEAF_SIZE - self.buffer.len()).min(bytes.len());
+            self.buffer.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.buffer.len() < TREE_LEAF_SIZE {
+                return;
+            }
+            let leaf = N::leaf(&self.buffer);
+            self.push_leaf(leaf);
+            self.buffer.clear();
+        }
+        let mut chunks = bytes.chunks_exact(TREE_LEAF_SIZE);
+        for chunk in &mut chunks {
+            self.push_leaf(N::leaf(chunk));
+        }
+        self.buffer.extend_from_slice(chunks.remainder());
+    }
+
+    fn finalize(&self) -> N {
+        let mut stack = self.stack.iter().rev().map(|&(node, _)| node);
+        let mut node = if !self.buffer.is_empty() || self.stack.is_empty() {
+            N::leaf(&self.buffer)
+        } else {
+            stack.next().unwrap()
+        };
+        for left in stack {
+            node = N::parent(left, node);
+        }
+        N::root(node, self.total_len)
+    }
+
+    fn reset(&mut self) {
+        self.stack.clear();
+        self.buffer.clear();
+        self.total_len = 0;
+    }
+}
+
+macro_rules! tree_hasher {
+    ($(#[$meta:meta])* $name:ident, $node:ty, $out:literal) => {
+        $(#[$meta])*
+        ///
+        /// The input is split into [`TREE_LEAF_SIZE`] leaves, the last one possibly
+        /// shorter, and an empty input is a single empty leaf. For `n > 1` leaves
+        /// the left child covers the largest power of two below `n` leaves and
+        /// the right child the rest. A parent hashes `0x01 ‖ left ‖ right` and the
+        /// root hashes `0x02 ‖ TREE_VERSION ‖ node ‖ total length as u64`, all
+        /// little-endian. The digest depends only on the input: the one-shot
+        /// [`hash`](Self::hash) (parallel with the `rayon` feature) and the
+        /// streaming form agree for any split of the data.
+        ///
+        /// This is a separate digest and does not equal the flat hash of the
+        /// same input.
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            state: TreeState<$node>,
+        }
+
+        impl $name {
+            /// Creates an empty streaming tree hasher.
+            pub fn new() -> Self {
+                $name {
+                    state: TreeState::new(),
+                }
+            }
+
+            #[doc = concat!("Computes the ", $out, " tree hash of `bytes` at once.")]
+            ///
+            /// # Arguments
+            ///
+            /// * `bytes` - The whole input.
+            ///
+            /// # Returns
+            ///
+            /// The root of the tree, equal to streaming the same bytes.
+            pub fn hash(bytes: &[u8]) -> $node {
+                tree_hash(bytes)
+            }
+
+            /// Appends `bytes` to the input; full leaves are hashed immediately.
+            pub fn update(&mut self, bytes: &[u8]) {
+                self.state.update(bytes);
+            }
+
+            /// Returns the root for the bytes written so far.
+            pub fn finalize(&self) -> $node {
+                self.state.finalize()
+            }
+
+            /// Clears the state to hash a new input.
+            pub fn reset(&mut self) {
+                self.state.reset();
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl std::io::Write for $name {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.update(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+    };
+}
+
+tree_hasher!(
+    /// Tree mode of [`city_hash128`](crate::city_hash128) for very large inputs.
+    CityTree128,
+    u128,
+    "128-bit"
+);
+
+tree_hasher!(
+    /// Tree mode of [`city_hash256_crc`](crate::city_hash256_crc) for very large inputs.
+    CityTree256,
+    Hash256,
+    "256-bit"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|i| {
+                (i as u64)
+                    .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+                    .rotate_left(23) as u8
+            })
+            .collect()
+    }
+
+    /// Reference shape: explicit recursion without the streaming stack.
+    fn reference<N: TreeNode>(bytes: &[u8]) -> N {
+        fn node<N: TreeNode>(bytes: &[u8], leaves: usize) -> N {
+            if leaves == 1 {
+                return N::leaf(bytes);
+            }
+            let mut left = 1;
+            while left * 2 < leaves {
+                left *= 2;
+            }
+            let (l, r) = bytes.split_at((left * TREE_LEAF_SIZE).min(bytes.len()));
+            N::parent(node(l, left), node(r, leaves - left))
+        }
+        let leaves = bytes.len().div_ceil(TREE_LEAF_SIZE).max(1);
+        N::root(node(bytes, leaves), bytes.len() as u64)
+    }
+
+    const LENGTHS: [usize; 9] = [
+        0,
+        1,
+        1000,
+        TREE_LEAF_SIZE,
+        TREE_LEAF_SIZE + 1,
+        2 * TREE_LEAF_SIZE,
+        3 * TREE_LEAF_SIZE - 5,
+        4 * TREE_LEAF_SIZE,
+        5 * TREE_LEAF_SIZE + 123,
+    ];
+
+    #[test]
+    fn test_tree_matches_reference() {
+        let input = data(*LENGTHS.iter().max().unwrap());
+        for len in LENGTHS {
+            let bytes = &input[..len];
+            assert_eq!(
+                CityTree128::hash(bytes),
+                reference::<u128>(bytes),
+                "len {len}"
+            );
+            assert_eq!(
+                CityTree256::hash(bytes),
+                reference::<Hash256>(bytes),
+                "len {len}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tree_streaming_matches_one_shot() {
+        let input = data(*LENGTHS.iter().max().unwrap());
+        for len in LENGTHS {
+            let bytes = &input[..len];
+            let expected128 = CityTree128::hash(bytes);
+            let expected256 = CityTree256::hash(bytes);
+            for step in [1usize << 20, 7777, 65536 + 3, len.max(1)] {
+                let mut h128 = CityTree128::new();
+                let mut h256 = CityTree256::new();
+                for chunk in bytes.chunks(step) {
+                    h128.update(chunk);
+                    h256.update(chunk);
+                }
+                assert_eq!(h128.finalize(), expected128, "len {len} step {step}");
+                assert_eq!(h256.finalize(), expected256, "len {len} step {step}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_tree_finalize_is_repeatable() {
+        let input = data(2 * TREE_LEAF_SIZE + 10);
+        let mut hasher = CityTree128::new();
+        hasher.update(&input[..TREE_LEAF_SIZE]);
+        let first = hasher.finalize();
+        assert_eq!(first, hasher.finalize());
+        assert_eq!(first, CityTree128::hash(&input[..TREE_LEAF_SIZE]));
+        hasher.update(&input[TREE_LEAF_SIZE..]);
+        assert_eq!(hasher.finalize(), CityTree128::hash(&input));
+        hasher.reset();
+        assert_eq!(hasher.finalize(), CityTree128::hash(&[]));
+    }
+
+    #[test]
+    fn test_tree_differs_by_length() {
+        let input = data(TREE_LEAF_SIZE);
+        assert_ne!(CityTree128::hash(&input[..0]), CityTree128::hash(&[0]));
+        assert_ne!(CityTree128::hash(&input), city_hash128(&input));
+    }
+
+    #[test]
+    fn test_tree_pinned() {
+        // Changing these values requires bumping TREE_VERSION.
+        assert_eq!(
+            CityTree128::hash(b"tree"),
+            0x157c09d67b7d88a7a9480ec3931dfd50
+        );
+        assert_eq!(
+            CityTree256::hash(b"tree"),
+            Hash256 {
+                lo: 340205821819954186658836163243744093004,
+                hi: 104968250966517660451259111862703587080,
+            }
+        );
+    }
+}
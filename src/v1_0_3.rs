@@ -0,0 +1,213 @@
+/*
+ * // Copyright (c) Radzivon Bartoshyk 10/2026. All rights reserved.
+ * //
+ * // Redistribution and use in source and binary forms, with or without modification,
+ * // are permitted provided that the following conditions are met:
+ * //
+ * // 1.  Redistributions of source code must retain the above copyright notice, this
+ * // list of conditions and the following disclaimer.
+ * //
+ * // 2.  Redistributions in binary form must reproduce the above copyright notice,
+ * // this list of conditions and the following disclaimer in the documentation
+ * // and/or other materials provided with the distribution.
+ * //
+ * // 3.  Neither the name of the copyright holder nor the names of its
+ * // contributors may be used to endorse or promote products derived from
+ * // this software without specific prior written permission.
+ * //
+ * // THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+ * // AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+ * // IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+ * // DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+ * // FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+ * // DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+ * // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+ * // CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+ * // OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+ * // OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+//! CityHash v1.0.3, bit for bit.
+//!
+//! This release reworked the long-input loops of v1.0.2; its CityHash64 for
+//! inputs over 64 bytes is the one kept by v1.1, while the short paths and
+//! CityHash128 still differ from the functions at the crate root.
+use crate::city64::{
+    K0, K1, K2, city_hash64_long, hash_len_16_u64, read_le64, weak_hash_len_32_with_seeds,
+};
+use crate::legacy::{legacy_hash64, legacy_hash128, legacy_murmur};
+
+/// Computes CityHash64 as of v1.0.3.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The value of `CityHash64` from the v1.0.3 release.
+pub fn city_hash64(bytes: &[u8]) -> u64 {
+    legacy_hash64(bytes, city_hash64_long)
+}
+
+/// Computes CityHash64WithSeed as of v1.0.3.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 64-bit seed mixed into the result.
+///
+/// # Returns
+///
+/// The value of `CityHash64WithSeed` from the v1.0.3 release.
+pub fn city_hash64_with_seed(bytes: &[u8], seed: u64) -> u64 {
+    hash_len_16_u64(city_hash64(bytes).wrapping_sub(K2), seed)
+}
+
+/// One 64-byte round of the v1.0.3 main loop, shared with v1.1.
+#[inline]
+fn round(
+    chunk: &[u8],
+    x: &mut u64,
+    y: &mut u64,
+    z: &mut u64,
+    v: &mut (u64, u64),
+    w: &mut (u64, u64),
+) {
+    *x = x
+        .wrapping_add(*y)
+        .wrapping_add(v.0)
+        .wrapping_add(read_le64(chunk, 8))
+        .rotate_right(37)
+        .wrapping_mul(K1);
+    *y = y
+        .wrapping_add(v.1)
+        .wrapping_add(read_le64(chunk, 48))
+        .rotate_right(42)
+        .wrapping_mul(K1);
+    *x ^= w.1;
+    *y = y.wrapping_add(v.0.wrapping_add(read_le64(chunk, 40)));
+    *z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(K1);
+    *v = weak_hash_len_32_with_seeds(chunk, 0, v.1.wrapping_mul(K1), x.wrapping_add(w.0));
+    *w = weak_hash_len_32_with_seeds(
+        chunk,
+        32,
+        z.wrapping_add(w.1),
+        y.wrapping_add(read_le64(chunk, 16)),
+    );
+    std::mem::swap(z, x);
+}
+
+/// Computes CityHash128WithSeed as of v1.0.3.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+/// * `seed` - A 128-bit seed; the low half is `Uint128Low64(seed)`.
+///
+/// # Returns
+///
+/// The value of `CityHash128WithSeed` with `Uint128Low64` in the low 64 bits.
+pub fn city_hash128_with_seed(bytes: &[u8], seed: u128) -> u128 {
+    if bytes.len() < 128 {
+        return legacy_murmur(bytes, seed);
+    }
+    // We expect len >= 128 to be the common case.  Keep 56 bytes of state:
+    // v, w, x, y, and z.
+    let mut x = seed as u64;
+    let mut y = (seed >> 64) as u64;
+    let mut z = (bytes.len() as u64).wrapping_mul(K1);
+    let v0 = (y ^ K1)
+        .rotate_right(49)
+        .wrapping_mul(K1)
+        .wrapping_add(read_le64(bytes, 0));
+    let mut v = (
+        v0,
+        v0.rotate_right(42)
+            .wrapping_mul(K1)
+            .wrapping_add(read_le64(bytes, 8)),
+    );
+    let mut w = (
+        y.wrapping_add(z)
+            .rotate_right(35)
+            .wrapping_mul(K1)
+            .wrapping_add(x),
+        x.wrapping_add(read_le64(bytes, 88))
+            .rotate_right(53)
+            .wrapping_mul(K1),
+    );
+
+    // This is the same inner loop as CityHash64(), manually unrolled.
+    let blocks = bytes.len() / 128 * 128;
+    for chunk in bytes[..blocks].chunks_exact(64) {
+        round(chunk, &mut x, &mut y, &mut z, &mut v, &mut w);
+    }
+    let tail = &bytes[blocks..];
+    x = x.wrapping_add(v.0.wrapping_add(z).rotate_right(49).wrapping_mul(K0));
+    z = z.wrapping_add(w.0.rotate_right(37).wrapping_mul(K0));
+    // If 0 < len < 128, hash up to 4 chunks of 32 bytes each from the end of s.
+    let mut tail_done = 0;
+    while tail_done < tail.len() {
+        tail_done += 32;
+        let start = bytes.len() - tail_done;
+        y = x
+            .wrapping_add(y)
+            .rotate_right(42)
+            .wrapping_mul(K0)
+            .wrapping_add(v.1);
+        w.0 = w.0.wrapping_add(read_le64(bytes, start + 16));
+        x = x.wrapping_mul(K0).wrapping_add(w.0);
+        z = z.wrapping_add(w.1.wrapping_add(read_le64(bytes, start)));
+        w.1 = w.1.wrapping_add(v.0);
+        v = weak_hash_len_32_with_seeds(bytes, start, v.0.wrapping_add(z), v.1);
+    }
+    // At this point our 56 bytes of state should contain more than
+    // enough information for a strong 128-bit hash.  We use two
+    // different 56-byte-to-8-byte hashes to get a 16-byte final result.
+    x = hash_len_16_u64(x, v.0);
+    y = hash_len_16_u64(y.wrapping_add(z), w.0);
+    let lo = hash_len_16_u64(x.wrapping_add(v.1), w.1).wrapping_add(y);
+    let hi = hash_len_16_u64(x.wrapping_add(w.1), y.wrapping_add(v.1));
+    (lo as u128) | ((hi as u128) << 64)
+}
+
+/// Computes CityHash128 as of v1.0.3.
+///
+/// # Arguments
+///
+/// * `bytes` - A slice of bytes to hash.
+///
+/// # Returns
+///
+/// The value of `CityHash128` with `Uint128Low64` in the low 64 bits.
+pub fn city_hash128(bytes: &[u8]) -> u128 {
+    legacy_hash128(bytes, city_hash128_with_seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::legacy::{legacy_test_data, legacy_test_rows, parse_table, test_input};
+
+    #[test]
+    fn test_city_test_table() {
+        let table = parse_table(include_str!("../testdata/city_v1_0_3.txt"));
+        assert_eq!(table.len(), 300);
+        let data = legacy_test_data();
+        let seed = 1234567u128 | ((K0 as u128) << 64);
+        for (i, expected) in table.iter().enumerate().take(legacy_test_rows()) {
+            let input = test_input(&data, i, table.len());
+            let h128 = city_hash128(input);
+            let s128 = city_hash128_with_seed(input, seed);
+            let actual = [
+                city_hash64(input),
+                city_hash64_with_seed(input, 1234567),
+                hash_len_16_u64(city_hash64(input).wrapping_sub(1234567), K0),
+                h128 as u64,
+                (h128 >> 64) as u64,
+                s128 as u64,
+                (s128 >> 64) as u64,
+            ];
+            assert_eq!(&actual, expected, "row {i}, length {}", input.len());
+        }
+    }
+}
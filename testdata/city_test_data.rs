@@ -0,0 +1,18 @@
+// The data generator of the v1.1 `city-test.cc`, also used by the farmhash.cc
+// self-test. Shared with `include!` by the crate's tests and the `diff` crate.
+
+/// The first `len` bytes of the 1 MiB of pseudo-random test data.
+pub(crate) fn city_test_bytes(len: usize) -> Vec<u8> {
+    const K0: u64 = 0xc3a5c85c97cb3127;
+    let mut a = 9u64;
+    let mut b = 777u64;
+    (0..len)
+        .map(|i| {
+            a = a.wrapping_add(b);
+            b = b.wrapping_add(a);
+            a = (a ^ (a >> 41)).wrapping_mul(K0);
+            b = (b ^ (b >> 41)).wrapping_mul(K0).wrapping_add(i as u64);
+            (b >> 37) as u8
+        })
+        .collect()
+}
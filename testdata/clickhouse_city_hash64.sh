@@ -0,0 +1,39 @@
+#!/bin/sh
+# Regenerates clickhouse_city_hash64.tsv by running every case through the
+# ClickHouse 24.8 LTS release; the header records its exact version:
+#
+#   sh testdata/clickhouse_city_hash64.sh > testdata/clickhouse_city_hash64.tsv
+#
+# Each case is an id, known to `test_clickhouse_vectors` in
+# src/clickhouse/mod.rs, and the arguments of `cityHash64` in SQL.
+set -eu
+
+IMAGE=clickhouse/clickhouse-server:24.8
+
+run() {
+    docker run --rm "$IMAGE" clickhouse local --query "$1"
+}
+
+echo "# cityHash64 outputs of ClickHouse $(run 'SELECT version()') ($IMAGE)."
+echo "# Columns: case, cityHash64 arguments, result. Regenerate with clickhouse_city_hash64.sh."
+while IFS='	' read -r id args; do
+    printf '%s\t%s\t%s\n' "$id" "$args" "$(run "SELECT cityHash64($args)")"
+done <<'CASES'
+empty_string	''
+string	'abc'
+long_string	repeat('x', 300)
+uint8	toUInt8(10)
+uint64_max	toUInt64(18446744073709551615)
+int8_negative	toInt8(-1)
+int16_negative	toInt16(-300)
+int32_negative	toInt32(-1)
+int64_min	toInt64(-9223372036854775808)
+fixed_string	toFixedString('ab', 5)
+tuple	(toUInt64(42), 'eu-west')
+multi_argument	toUInt64(42), 'eu-west'
+empty_array	CAST([], 'Array(UInt32)')
+array	[toUInt32(1), toUInt32(2)]
+array_of_tuples	[(toUInt8(1), 'a'), (toUInt8(2), 'b')]
+nested_array	[[toUInt32(1), toUInt32(2)], [], [toUInt32(3)]]
+documented_example	array('e', 'x', 'a'), 'mple', 10, toDateTime('2019-06-15 23:00:00', 'Europe/Moscow')
+CASES
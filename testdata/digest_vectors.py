@@ -0,0 +1,76 @@
+#!/usr/bin/env python3
+# Regenerates the blobby test vectors of tests/digest.rs, for
+# `digest::new_test!`, from the city-test.cc table in city_v1_1.txt:
+#
+#   python3 testdata/digest_vectors.py
+#
+# Each file holds (input, digest) pairs for the first 299 table inputs; the
+# last one, the whole 1 MiB of test data, is left out. Digests are the 64-bit
+# words of the result in C++ API order, each little-endian.
+import os
+import struct
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+MASK = (1 << 64) - 1
+K0 = 0xC3A5C85C97CB3127
+
+# Columns of city_v1_1.txt per digest type.
+COLUMNS = {
+    "city64": [0],
+    "city128": [3, 4],
+    "city256_crc": [11, 12, 13, 14],
+}
+
+
+def city_test_bytes(length):
+    """The city-test.cc data generator, as in city_test_data.rs."""
+    a, b = 9, 777
+    out = bytearray()
+    for i in range(length):
+        a = (a + b) & MASK
+        b = (b + a) & MASK
+        a = ((a ^ (a >> 41)) * K0) & MASK
+        b = (((b ^ (b >> 41)) * K0) + i) & MASK
+        out.append((b >> 37) & 0xFF)
+    return bytes(out)
+
+
+def vlq(value):
+    """The variable-length integers of the blobby format."""
+    out = [value & 0x7F]
+    value >>= 7
+    while value:
+        value -= 1
+        out.append(0x80 | (value & 0x7F))
+        value >>= 7
+    return bytes(reversed(out))
+
+
+def blobby(blobs):
+    """A blobby file without deduplicated blobs."""
+    out = bytearray(vlq(0))
+    for blob in blobs:
+        out += vlq(len(blob) << 1) + blob
+    return bytes(out)
+
+
+def main():
+    with open(os.path.join(HERE, "city_v1_1.txt")) as table:
+        rows = [
+            [int(word, 16) for word in line.split()]
+            for line in table
+            if line.strip() and not line.startswith("#")
+        ]
+    data = city_test_bytes(len(rows) ** 2)
+    for name, columns in COLUMNS.items():
+        blobs = []
+        for i, row in enumerate(rows[:-1]):
+            blobs.append(data[i * i : i * i + i])
+            blobs.append(b"".join(struct.pack("<Q", row[c]) for c in columns))
+        path = os.path.join(HERE, "..", "tests", "data", name + ".blb")
+        with open(path, "wb") as out:
+            out.write(blobby(blobs))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,56 @@
+//! Checks `force_backend`. Forcing a backend is process wide, so this lives in
+//! its own binary, and in a single test so nothing runs while it switches.
+use cith::{
+    BACKEND_ENV_VAR, Backend, BackendUnavailable, backend, city_hash128_crc, city_hash256_crc,
+    crc32c::crc32c, force_backend,
+};
+
+#[test]
+fn test_force_backend() {
+    let initial = backend();
+    if let Some(forced) = std::env::var(BACKEND_ENV_VAR)
+        .ok()
+        .and_then(|name| name.parse::<Backend>().ok())
+        .filter(|backend| backend.is_available())
+    {
+        assert_eq!(initial, forced);
+    }
+
+    let data: Vec<u8> = (0..4096u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .collect();
+    let expected: Vec<_> = (0..=data.len())
+        .step_by(7)
+        .map(|len| {
+            let bytes = &data[..len];
+            (
+                city_hash128_crc(bytes),
+                city_hash256_crc(bytes),
+                crc32c(bytes),
+            )
+        })
+        .collect();
+
+    let available = Backend::available();
+    for backend in Backend::ALL {
+        if !available.contains(&backend) {
+            assert_eq!(force_backend(backend), Err(BackendUnavailable(backend)));
+            continue;
+        }
+        force_backend(backend).unwrap();
+        assert_eq!(cith::backend(), backend);
+        for (i, len) in (0..=data.len()).step_by(7).enumerate() {
+            let bytes = &data[..len];
+            assert_eq!(
+                (
+                    city_hash128_crc(bytes),
+                    city_hash256_crc(bytes),
+                    crc32c(bytes)
+                ),
+                expected[i],
+                "length {len} on {backend}"
+            );
+        }
+    }
+    force_backend(initial).unwrap();
+}
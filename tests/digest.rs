@@ -0,0 +1,10 @@
+//! Checks the `digest` types against the city-test.cc table with the
+//! RustCrypto test harness. Regenerate `data/*.blb` with
+//! `testdata/digest_vectors.py`.
+#![cfg(feature = "digest")]
+use cith::digest::{City64, City128, City256Crc};
+use digest::dev::fixed_test;
+
+digest::new_test!(test_city64, "city64", City64, fixed_test);
+digest::new_test!(test_city128, "city128", City128, fixed_test);
+digest::new_test!(test_city256_crc, "city256_crc", City256Crc, fixed_test);